@@ -2,6 +2,7 @@
 fn main() {
     println!("cargo:rustc-link-lib=X11");
     println!("cargo:rustc-link-lib=Xinerama");
+    println!("cargo:rustc-link-lib=Xrandr");
     println!("cargo:rustc-link-lib=Xfixes");
     println!("cargo:rustc-link-lib=Xft");
 }
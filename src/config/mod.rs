@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Padding {
     pub top: i32,
     pub bottom: i32,
@@ -13,6 +14,39 @@ pub struct Padding {
     pub right: i32,
 }
 
+/*
+ * The settings a workspace starts with, `[workspace.N]` tables override the `[monitor."NAME"]` tables
+ * which override the global values
+*/
+pub struct WorkspaceConfig {
+    pub padding: Padding,
+    pub inner_gap: u32,
+    pub outer_gap: u32,
+    pub master_count: usize,
+    pub master_ratio: f32,
+    pub default_layout: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MonitorTarget {
+    Next,
+    Prev,
+    Index(usize),
+}
+
+impl MonitorTarget {
+    /*
+     * Monitors are numbered from 1 in the config
+    */
+    pub fn parse(argument: &str) -> Option<MonitorTarget> {
+        match argument.trim() {
+            "next" => Some(MonitorTarget::Next),
+            "prev" => Some(MonitorTarget::Prev),
+            index => index.parse::<usize>().ok().filter(|x| *x > 0).map(|x| MonitorTarget::Index(x - 1)),
+        }
+    }
+}
+
 pub enum Internal {
     Fullscreen,
     Kill,
@@ -25,6 +59,31 @@ pub enum Internal {
     WindowDown,
     WindowMaster,
     ToggleFloat,
+    ToggleSticky,
+    IncMaster,
+    DecMaster,
+    GrowMaster,
+    ShrinkMaster,
+    IncGaps,
+    DecGaps,
+    WorkspaceNext,
+    WorkspacePrev,
+    WorkspaceLast,
+    MoveToWorkspace(usize),
+    MoveToWorkspaceAndFollow(usize),
+    ToggleView(usize),
+    ToggleTag(usize),
+    MoveWorkspaceToMonitor(MonitorTarget),
+    FocusMonitor(MonitorTarget),
+    SendToMonitor(MonitorTarget),
+    CycleLayout,
+    SetLayout(String),
+    PreselHorizontal,
+    PreselVertical,
+    RotateSplit,
+    FlipSplit,
+    GrowSplit,
+    ShrinkSplit,
 }
 
 pub enum Action {
@@ -35,6 +94,20 @@ pub enum Action {
 pub struct Config {
     pub bar: bool,
     pub padding: Padding,
+    pub master_count: usize,
+    pub master_ratio: f32,
+    pub inner_gap: u32,
+    pub outer_gap: u32,
+    pub smart_gaps: bool,
+    pub default_layout: String,
+    pub workspaces: Vec<String>,
+    pub back_and_forth: bool,
+    pub dynamic_workspaces: bool,
+    pub tags: bool,
+    pub shared_workspaces: bool,
+    pub focus_follows_pointer_monitor: bool,
+    pub workspace_overrides: Table,
+    pub monitor_overrides: Table,
     pub keybindings: HashMap<u32, Action>,
 }
 
@@ -48,11 +121,27 @@ impl Config {
             Ok(Config {
                 bar: Self::get_bool(&config, "default-bar", true),
                 padding: Padding {
-                    top:    Self::get_int(&config, "top-padding",       50) as i32,
+                    top:    Self::get_int(&config, "top-padding",       10) as i32,
                     bottom: Self::get_int(&config, "bottom-padding",    10) as i32,
                     left:   Self::get_int(&config, "left-padding",      10) as i32,
                     right:  Self::get_int(&config, "right-padding",     10) as i32,
                 },
+                master_count: Self::get_int(&config, "master-count", 1),
+                master_ratio: Self::get_float(&config, "master-ratio", 0.5).clamp(0.1, 0.9) as f32,
+                inner_gap: Self::get_int(&config, "inner-gap", 10) as u32,
+                outer_gap: Self::get_int(&config, "outer-gap", 0) as u32,
+                smart_gaps: Self::get_bool(&config, "smart-gaps", false),
+                default_layout: Self::get_string(&config, "default-layout", "master-stack"),
+                workspaces: Self::get_strings(&config, "workspaces", &["1", "2", "3", "4"]),
+                back_and_forth: Self::get_bool(&config, "workspace-back-and-forth", false),
+                dynamic_workspaces: Self::get_bool(&config, "dynamic-workspaces", false)
+                    && !Self::get_bool(&config, "tags", false)
+                    && !Self::get_bool(&config, "shared-workspaces", false),
+                tags: Self::get_bool(&config, "tags", false),
+                shared_workspaces: Self::get_bool(&config, "shared-workspaces", false) && !Self::get_bool(&config, "tags", false),
+                focus_follows_pointer_monitor: Self::get_bool(&config, "focus-follows-pointer-monitor", false),
+                workspace_overrides: config.get("workspace").and_then(|x| x.as_table()).cloned().unwrap_or_default(),
+                monitor_overrides: config.get("monitor").and_then(|x| x.as_table()).cloned().unwrap_or_default(),
                 keybindings: Self::get_keybindings(&config)?,
             })
         } else {
@@ -64,16 +153,83 @@ impl Config {
             Ok(Config {
                 bar: true,
                 padding: Padding {
-                    top:    50,
+                    top:    10,
                     bottom: 10,
                     left:   10,
                     right:  10,
                 },
+                master_count: 1,
+                master_ratio: 0.5,
+                inner_gap: 10,
+                outer_gap: 0,
+                smart_gaps: false,
+                default_layout: String::from("master-stack"),
+                workspaces: vec![String::from("1"), String::from("2"), String::from("3"), String::from("4")],
+                back_and_forth: false,
+                dynamic_workspaces: false,
+                tags: false,
+                shared_workspaces: false,
+                focus_follows_pointer_monitor: false,
+                workspace_overrides: Table::new(),
+                monitor_overrides: Table::new(),
                 keybindings,
             })
         }
     }
 
+    /*
+     * Monitors are overridden by the name of their RandR output
+    */
+    fn monitor_table(&self, monitor: &str) -> Table {
+        self.monitor_overrides.get(monitor)
+            .and_then(|x| x.as_table())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn monitor_bar(&self, monitor: &str) -> bool {
+        Self::get_bool(&self.monitor_table(monitor), "default-bar", self.bar)
+    }
+
+    pub fn workspace_names(&self, monitor: &str) -> Vec<String> {
+        let workspaces = self.workspaces.iter().map(|x| x.as_str()).collect::<Vec<&str>>();
+
+        Self::get_strings(&self.monitor_table(monitor), "workspaces", &workspaces)
+    }
+
+    pub fn workspace_name(&self, monitor: &str, index: usize) -> String {
+        self.workspace_names(monitor).get(index).cloned().unwrap_or_else(|| format!("{}", index + 1))
+    }
+
+    /*
+     * Workspaces are overridden by their number starting from 1 or by their name
+    */
+    pub fn workspace_config(&self, monitor: &str, index: usize) -> WorkspaceConfig {
+        let mut overrides = self.monitor_table(monitor);
+
+        overrides.extend(
+            self.workspace_overrides.get(&format!("{}", index + 1))
+                .or_else(|| self.workspace_overrides.get(&self.workspace_name(monitor, index)))
+                .and_then(|x| x.as_table())
+                .cloned()
+                .unwrap_or_default()
+        );
+
+        WorkspaceConfig {
+            padding: Padding {
+                top:    Self::get_int(&overrides, "top-padding",       self.padding.top as usize) as i32,
+                bottom: Self::get_int(&overrides, "bottom-padding",    self.padding.bottom as usize) as i32,
+                left:   Self::get_int(&overrides, "left-padding",      self.padding.left as usize) as i32,
+                right:  Self::get_int(&overrides, "right-padding",     self.padding.right as usize) as i32,
+            },
+            inner_gap: Self::get_int(&overrides, "inner-gap", self.inner_gap as usize) as u32,
+            outer_gap: Self::get_int(&overrides, "outer-gap", self.outer_gap as usize) as u32,
+            master_count: Self::get_int(&overrides, "master-count", self.master_count),
+            master_ratio: Self::get_float(&overrides, "master-ratio", self.master_ratio as f64).clamp(0.1, 0.9) as f32,
+            default_layout: Self::get_string(&overrides, "default-layout", &self.default_layout),
+        }
+    }
+
     pub fn get_keybindings(config: &toml::map::Map<String, toml::Value>) -> Result<HashMap<u32, Action>, Box<dyn std::error::Error>> {
         let mut keybindings: HashMap<u32, Action> = HashMap::new();
 
@@ -85,7 +241,10 @@ impl Config {
                     if let Some(exec) = table.get("exec") {
                         keybindings.insert(key, Action::Exec(exec.as_str().unwrap_or_default().to_string()));
                     } else if let Some(internal) = table.get("internal") {
-                        match internal.as_str().unwrap_or_default() {
+                        let internal = internal.as_str().unwrap_or_default();
+                        let (internal, argument) = internal.split_once(' ').unwrap_or((internal, ""));
+
+                        match internal {
                             "fullscreen" => { keybindings.insert(key, Action::Internal(Internal::Fullscreen)); },
                             "kill" => { keybindings.insert(key, Action::Internal(Internal::Kill)); },
                             "exit" => { keybindings.insert(key, Action::Internal(Internal::Exit)); },
@@ -97,6 +256,51 @@ impl Config {
                             "window_down" => { keybindings.insert(key, Action::Internal(Internal::WindowDown)); },
                             "window_master" => { keybindings.insert(key, Action::Internal(Internal::WindowMaster)); },
                             "toggle_float" => { keybindings.insert(key, Action::Internal(Internal::ToggleFloat)); },
+                            "toggle_sticky" => { keybindings.insert(key, Action::Internal(Internal::ToggleSticky)); },
+                            "inc_master" => { keybindings.insert(key, Action::Internal(Internal::IncMaster)); },
+                            "dec_master" => { keybindings.insert(key, Action::Internal(Internal::DecMaster)); },
+                            "grow_master" => { keybindings.insert(key, Action::Internal(Internal::GrowMaster)); },
+                            "shrink_master" => { keybindings.insert(key, Action::Internal(Internal::ShrinkMaster)); },
+                            "inc_gaps" => { keybindings.insert(key, Action::Internal(Internal::IncGaps)); },
+                            "dec_gaps" => { keybindings.insert(key, Action::Internal(Internal::DecGaps)); },
+                            "workspace_next" => { keybindings.insert(key, Action::Internal(Internal::WorkspaceNext)); },
+                            "workspace_prev" => { keybindings.insert(key, Action::Internal(Internal::WorkspacePrev)); },
+                            "workspace_last" => { keybindings.insert(key, Action::Internal(Internal::WorkspaceLast)); },
+                            "move_to_workspace" | "move_to_workspace_and_follow" | "toggle_view" | "toggle_tag" => {
+                                if let Some(workspace) = argument.trim().parse::<usize>().ok().filter(|x| *x > 0).map(|x| x - 1) {
+                                    let internal = match internal {
+                                        "move_to_workspace" => Internal::MoveToWorkspace(workspace),
+                                        "move_to_workspace_and_follow" => Internal::MoveToWorkspaceAndFollow(workspace),
+                                        "toggle_view" => Internal::ToggleView(workspace),
+                                        _ => Internal::ToggleTag(workspace),
+                                    };
+
+                                    keybindings.insert(key, Action::Internal(internal));
+                                } else {
+                                    println!("[+] invalid workspace: {}", argument);
+                                }
+                            },
+                            "move_workspace_to_monitor" | "focus_monitor" | "send_to_monitor" => {
+                                if let Some(target) = MonitorTarget::parse(argument) {
+                                    let internal = match internal {
+                                        "move_workspace_to_monitor" => Internal::MoveWorkspaceToMonitor(target),
+                                        "focus_monitor" => Internal::FocusMonitor(target),
+                                        _ => Internal::SendToMonitor(target),
+                                    };
+
+                                    keybindings.insert(key, Action::Internal(internal));
+                                } else {
+                                    println!("[+] invalid monitor: {}", argument);
+                                }
+                            },
+                            "cycle_layout" => { keybindings.insert(key, Action::Internal(Internal::CycleLayout)); },
+                            "set_layout" => { keybindings.insert(key, Action::Internal(Internal::SetLayout(argument.trim().to_string()))); },
+                            "presel_horizontal" => { keybindings.insert(key, Action::Internal(Internal::PreselHorizontal)); },
+                            "presel_vertical" => { keybindings.insert(key, Action::Internal(Internal::PreselVertical)); },
+                            "rotate_split" => { keybindings.insert(key, Action::Internal(Internal::RotateSplit)); },
+                            "flip_split" => { keybindings.insert(key, Action::Internal(Internal::FlipSplit)); },
+                            "grow_split" => { keybindings.insert(key, Action::Internal(Internal::GrowSplit)); },
+                            "shrink_split" => { keybindings.insert(key, Action::Internal(Internal::ShrinkSplit)); },
                             internal => println!("[+] unknown internal: {}", internal),
                         }
                     } else {
@@ -113,6 +317,20 @@ impl Config {
         config.get(key).map_or(default, |x| x.as_integer().unwrap_or_default() as usize)
     }
 
+    pub fn get_float(config: &toml::map::Map<String, toml::Value>, key: &str, default: f64) -> f64 {
+        config.get(key).map_or(default, |x| x.as_float().unwrap_or(default))
+    }
+
+    pub fn get_string(config: &toml::map::Map<String, toml::Value>, key: &str, default: &str) -> String {
+        config.get(key).map_or(default, |x| x.as_str().unwrap_or(default)).to_string()
+    }
+
+    pub fn get_strings(config: &toml::map::Map<String, toml::Value>, key: &str, default: &[&str]) -> Vec<String> {
+        config.get(key)
+            .and_then(|x| x.as_array())
+            .map_or(default.iter().map(|x| x.to_string()).collect(), |x| x.iter().map(|x| x.as_str().unwrap_or_default().to_string()).collect())
+    }
+
     pub fn get_bool(config: &toml::map::Map<String, toml::Value>, key: &str, default: bool) -> bool {
         config.get(key).map_or(default, |x| x.as_bool().unwrap_or_default())
     }
@@ -0,0 +1,29 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+pub struct BottomStack;
+
+impl Layout for BottomStack {
+    fn name(&self) -> &str {
+        "bottom-stack"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect> {
+        let gap = workspace.inner_gap;
+        let masters = workspace.master_count.min(clients.len());
+
+        if masters == 0 || masters == clients.len() {
+            return area.split_columns(clients.len(), gap);
+        }
+
+        let master_height = (area.height.saturating_sub(gap) as f32 * workspace.master_ratio) as u32;
+
+        let master = Rect::new(area.x, area.y, area.width, master_height);
+        let stack = Rect::new(area.x, area.y + (master_height + gap) as i32, area.width, area.height.saturating_sub(master_height + gap));
+
+        let mut rects = master.split_columns(masters, gap);
+        rects.extend(stack.split_columns(clients.len() - masters, gap));
+
+        rects
+    }
+}
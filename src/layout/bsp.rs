@@ -0,0 +1,243 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+use std::mem;
+
+/*
+ * Horizontal places the two children of a split next to each other,
+ * Vertical places them on top of each other
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Split {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Leaf(u64),
+    Split {
+        split: Split,
+        ratio: f32,
+        first: Box<Node>,
+        second: Box<Node>,
+    },
+}
+
+impl Node {
+    pub fn contains(&self, window: u64) -> bool {
+        match self {
+            Node::Leaf(leaf) => *leaf == window,
+            Node::Split { first, second, .. } => first.contains(window) || second.contains(window),
+        }
+    }
+
+    pub fn windows(&self) -> Vec<u64> {
+        match self {
+            Node::Leaf(leaf) => vec![*leaf],
+            Node::Split { first, second, .. } => {
+                let mut windows = first.windows();
+                windows.extend(second.windows());
+
+                windows
+            },
+        }
+    }
+
+    /*
+     * Replaces the leaf holding `target` with a split of `target` and `window`,
+     * returns false if `target` is not in the tree
+    */
+    pub fn insert(&mut self, target: u64, window: u64, split: Split) -> bool {
+        match self {
+            Node::Leaf(leaf) if *leaf == target => {
+                *self = Node::Split {
+                    split,
+                    ratio: 0.5,
+                    first: Box::new(Node::Leaf(target)),
+                    second: Box::new(Node::Leaf(window)),
+                };
+
+                true
+            },
+            Node::Leaf(_) => false,
+            Node::Split { first, second, .. } => first.insert(target, window, split) || second.insert(target, window, split),
+        }
+    }
+
+    /*
+     * Removes the leaf holding `window`, its sibling takes the place of the parent split
+    */
+    pub fn remove(self, window: u64) -> Option<Node> {
+        match self {
+            Node::Leaf(leaf) if leaf == window => None,
+            Node::Leaf(leaf) => Some(Node::Leaf(leaf)),
+            Node::Split { split, ratio, first, second } => {
+                match (first.remove(window), second.remove(window)) {
+                    (Some(first), Some(second)) => Some(Node::Split { split, ratio, first: Box::new(first), second: Box::new(second) }),
+                    (first, second) => first.or(second),
+                }
+            },
+        }
+    }
+
+    /*
+     * Returns the split directly above the leaf holding `window` and whether the leaf is its first child
+    */
+    pub fn parent_mut(&mut self, window: u64) -> Option<(&mut Node, bool)> {
+        let position = match self {
+            Node::Split { first, second, .. } => {
+                if **first == Node::Leaf(window) {
+                    Some(true)
+                } else if **second == Node::Leaf(window) {
+                    Some(false)
+                } else {
+                    None
+                }
+            },
+            Node::Leaf(_) => return None,
+        };
+
+        if let Some(is_first) = position {
+            return Some((self, is_first));
+        }
+
+        match self {
+            Node::Split { first, second, .. } => first.parent_mut(window).or_else(|| second.parent_mut(window)),
+            Node::Leaf(_) => None,
+        }
+    }
+
+    pub fn rotate(&mut self) {
+        if let Node::Split { split, .. } = self {
+            *split = match split {
+                Split::Horizontal => Split::Vertical,
+                Split::Vertical => Split::Horizontal,
+            };
+        }
+    }
+
+    pub fn flip(&mut self) {
+        if let Node::Split { first, second, .. } = self {
+            mem::swap(first, second);
+        }
+    }
+
+    pub fn resize(&mut self, delta: f32) {
+        if let Node::Split { ratio, .. } = self {
+            *ratio = (*ratio + delta).clamp(0.1, 0.9);
+        }
+    }
+
+    fn arrange(&self, area: Rect, gap: u32, rects: &mut Vec<(u64, Rect)>) {
+        match self {
+            Node::Leaf(leaf) => rects.push((*leaf, area)),
+            Node::Split { split: Split::Horizontal, ratio, first, second } => {
+                let width = (area.width.saturating_sub(gap) as f32 * ratio) as u32;
+
+                first.arrange(Rect::new(area.x, area.y, width, area.height), gap, rects);
+                second.arrange(Rect::new(area.x + (width + gap) as i32, area.y, area.width.saturating_sub(width + gap), area.height), gap, rects);
+            },
+            Node::Split { split: Split::Vertical, ratio, first, second } => {
+                let height = (area.height.saturating_sub(gap) as f32 * ratio) as u32;
+
+                first.arrange(Rect::new(area.x, area.y, area.width, height), gap, rects);
+                second.arrange(Rect::new(area.x, area.y + (height + gap) as i32, area.width, area.height.saturating_sub(height + gap)), gap, rects);
+            },
+        }
+    }
+}
+
+pub struct Bsp;
+
+impl Layout for Bsp {
+    fn name(&self) -> &str {
+        "bsp"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect> {
+        let mut rects: Vec<(u64, Rect)> = Vec::new();
+
+        if let Some(tree) = &workspace.tree {
+            tree.arrange(area, workspace.inner_gap, &mut rects);
+        }
+
+        clients.iter()
+            .map(|client| rects.iter().find(|(window, _)| *window == client.window).map_or(area, |(_, rect)| *rect))
+            .collect::<Vec<Rect>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::tests::{clients, workspace};
+
+    fn split(split: Split, first: Node, second: Node) -> Node {
+        Node::Split {
+            split,
+            ratio: 0.5,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    #[test]
+    fn insert_splits_the_target_leaf() {
+        let mut tree = Node::Leaf(1);
+
+        assert!(tree.insert(1, 2, Split::Horizontal));
+        assert!(tree.insert(2, 3, Split::Vertical));
+        assert!(!tree.insert(4, 5, Split::Vertical));
+
+        assert_eq!(tree, split(Split::Horizontal, Node::Leaf(1), split(Split::Vertical, Node::Leaf(2), Node::Leaf(3))));
+        assert_eq!(tree.windows(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn remove_promotes_the_sibling() {
+        let tree = split(Split::Horizontal, Node::Leaf(1), split(Split::Vertical, Node::Leaf(2), Node::Leaf(3)));
+
+        assert_eq!(tree.clone().remove(2), Some(split(Split::Horizontal, Node::Leaf(1), Node::Leaf(3))));
+        assert_eq!(tree.clone().remove(1), Some(split(Split::Vertical, Node::Leaf(2), Node::Leaf(3))));
+        assert_eq!(tree.clone().remove(4), Some(tree));
+        assert_eq!(Node::Leaf(1).remove(1), None);
+    }
+
+    #[test]
+    fn parent_mut_finds_the_split_above_a_leaf() {
+        let mut tree = split(Split::Horizontal, Node::Leaf(1), split(Split::Vertical, Node::Leaf(2), Node::Leaf(3)));
+
+        assert!(matches!(tree.parent_mut(1), Some((Node::Split { split: Split::Horizontal, .. }, true))));
+        assert!(matches!(tree.parent_mut(3), Some((Node::Split { split: Split::Vertical, .. }, false))));
+        assert!(tree.parent_mut(4).is_none());
+        assert!(Node::Leaf(1).parent_mut(1).is_none());
+    }
+
+    #[test]
+    fn arrange_follows_the_tree() {
+        let mut workspace = workspace(10);
+
+        workspace.tree = Some(split(Split::Horizontal, Node::Leaf(1), split(Split::Vertical, Node::Leaf(2), Node::Leaf(3))));
+
+        assert_eq!(Bsp.arrange(Rect::new(0, 0, 1910, 1010), &clients(3), &workspace), vec![
+            Rect::new(0, 0, 950, 1010),
+            Rect::new(960, 0, 950, 500),
+            Rect::new(960, 510, 950, 500),
+        ]);
+    }
+
+    #[test]
+    fn deep_trees_do_not_underflow() {
+        let mut workspace = workspace(10);
+        let mut tree = Node::Leaf(1);
+
+        for window in 2..=20 {
+            tree.insert(window - 1, window, if window % 2 == 0 { Split::Horizontal } else { Split::Vertical });
+        }
+
+        workspace.tree = Some(tree);
+
+        assert_eq!(Bsp.arrange(Rect::new(0, 0, 1900, 1000), &clients(20), &workspace).len(), 20);
+    }
+}
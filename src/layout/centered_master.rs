@@ -0,0 +1,92 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+pub struct CenteredMaster;
+
+impl Layout for CenteredMaster {
+    fn name(&self) -> &str {
+        "centered-master"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect> {
+        let gap = workspace.inner_gap;
+        let masters = workspace.master_count.min(clients.len());
+        let stacked = clients.len() - masters;
+
+        if masters == 0 || stacked == 0 {
+            return area.split_rows(clients.len(), gap);
+        }
+
+        let master_width = (area.width.saturating_sub(gap) as f32 * workspace.master_ratio) as u32;
+
+        /*
+         * with a single stack client there is nothing to balance the master against,
+         * so it is placed to the right of the master like in the master-stack layout
+        */
+        if stacked == 1 {
+            let master = Rect::new(area.x, area.y, master_width, area.height);
+            let stack = Rect::new(area.x + (master_width + gap) as i32, area.y, area.width.saturating_sub(master_width + gap), area.height);
+
+            let mut rects = master.split_rows(masters, gap);
+            rects.push(stack);
+
+            return rects;
+        }
+
+        let master_width = master_width.min(area.width.saturating_sub(gap * 2));
+        let side_width = area.width.saturating_sub(master_width + gap * 2) / 2;
+
+        let left = Rect::new(area.x, area.y, side_width, area.height);
+        let master = Rect::new(area.x + (side_width + gap) as i32, area.y, master_width, area.height);
+        let right = Rect::new(area.x + (side_width + master_width + gap * 2) as i32, area.y, area.width.saturating_sub(side_width + master_width + gap * 2), area.height);
+
+        let mut rects = master.split_rows(masters, gap);
+        let mut right = right.split_rows(stacked - stacked / 2, gap).into_iter();
+        let mut left = left.split_rows(stacked / 2, gap).into_iter();
+
+        /*
+         * stack clients alternate between the right and the left column starting with the right
+        */
+        for index in 0..stacked {
+            rects.extend(if index % 2 == 0 { right.next() } else { left.next() });
+        }
+
+        rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::tests::{clients, workspace};
+
+    #[test]
+    fn stack_clients_alternate_between_right_and_left() {
+        assert_eq!(CenteredMaster.arrange(Rect::new(0, 0, 1910, 1010), &clients(4), &workspace(10)), vec![
+            Rect::new(480, 0, 950, 1010),
+            Rect::new(1440, 0, 470, 500),
+            Rect::new(0, 0, 470, 1010),
+            Rect::new(1440, 510, 470, 500),
+        ]);
+    }
+
+    #[test]
+    fn single_stack_client_is_placed_right_of_the_master() {
+        assert_eq!(CenteredMaster.arrange(Rect::new(0, 0, 1910, 1010), &clients(2), &workspace(10)), vec![
+            Rect::new(0, 0, 950, 1010),
+            Rect::new(960, 0, 950, 1010),
+        ]);
+    }
+
+    #[test]
+    fn master_leaves_room_for_the_gaps() {
+        let mut workspace = workspace(10);
+
+        workspace.master_ratio = 1.0;
+
+        let rects = CenteredMaster.arrange(Rect::new(0, 0, 1910, 1010), &clients(3), &workspace);
+
+        assert_eq!(rects[0], Rect::new(10, 0, 1890, 1010));
+        assert_eq!(rects.len(), 3);
+    }
+}
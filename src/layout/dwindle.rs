@@ -0,0 +1,67 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+pub struct Dwindle;
+
+impl Layout for Dwindle {
+    fn name(&self) -> &str {
+        "dwindle"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect> {
+        let gap = workspace.inner_gap;
+        let mut rects: Vec<Rect> = Vec::new();
+        let mut rest = area;
+
+        for index in 0..clients.len() {
+            if index == clients.len() - 1 {
+                rects.push(rest);
+            } else if index % 2 == 0 {
+                let width = rest.width.saturating_sub(gap) / 2;
+
+                rects.push(Rect::new(rest.x, rest.y, width, rest.height));
+                rest = Rect::new(rest.x + (width + gap) as i32, rest.y, rest.width.saturating_sub(width + gap), rest.height);
+            } else {
+                let height = rest.height.saturating_sub(gap) / 2;
+
+                rects.push(Rect::new(rest.x, rest.y, rest.width, height));
+                rest = Rect::new(rest.x, rest.y + (height + gap) as i32, rest.width, rest.height.saturating_sub(height + gap));
+            }
+        }
+
+        rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::tests::{clients, workspace};
+
+    #[test]
+    fn single_client_fills_area() {
+        let area = Rect::new(0, 0, 1900, 1000);
+
+        assert_eq!(Dwindle.arrange(area, &clients(1), &workspace(10)), vec![area]);
+    }
+
+    #[test]
+    fn alternates_between_columns_and_rows() {
+        let rects = Dwindle.arrange(Rect::new(0, 0, 1900, 1000), &clients(3), &workspace(10));
+
+        assert_eq!(rects, vec![
+            Rect::new(0, 0, 945, 1000),
+            Rect::new(955, 0, 945, 495),
+            Rect::new(955, 505, 945, 495),
+        ]);
+    }
+
+    #[test]
+    fn cells_smaller_than_gap_do_not_underflow() {
+        let area = Rect::new(0, 0, 1900, 1000);
+        let rects = Dwindle.arrange(area, &clients(20), &workspace(10));
+
+        assert_eq!(rects.len(), 20);
+        assert!(rects.iter().all(|rect| rect.width <= area.width && rect.height <= area.height));
+    }
+}
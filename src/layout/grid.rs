@@ -0,0 +1,71 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+pub struct Grid;
+
+impl Layout for Grid {
+    fn name(&self) -> &str {
+        "grid"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect> {
+        if clients.is_empty() {
+            return Vec::new();
+        }
+
+        let gap = workspace.inner_gap;
+        let columns = (clients.len() as f32).sqrt().ceil() as usize;
+        let mut rects: Vec<Rect> = Vec::new();
+
+        /*
+         * when the clients dont fill the grid the rightmost columns get an extra row
+         * so that no empty cells are left behind
+        */
+        for (index, column) in area.split_columns(columns, gap).iter().enumerate() {
+            let extra = (index >= columns - clients.len() % columns) as usize;
+
+            rects.extend(column.split_rows(clients.len() / columns + extra, gap));
+        }
+
+        rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::tests::{clients, workspace};
+
+    #[test]
+    fn three_clients_give_the_right_column_an_extra_row() {
+        assert_eq!(Grid.arrange(Rect::new(0, 0, 1910, 1010), &clients(3), &workspace(10)), vec![
+            Rect::new(0, 0, 950, 1010),
+            Rect::new(960, 0, 950, 500),
+            Rect::new(960, 510, 950, 500),
+        ]);
+    }
+
+    #[test]
+    fn five_clients_give_the_two_rightmost_columns_an_extra_row() {
+        assert_eq!(Grid.arrange(Rect::new(0, 0, 1910, 1010), &clients(5), &workspace(10)), vec![
+            Rect::new(0, 0, 630, 1010),
+            Rect::new(640, 0, 630, 500),
+            Rect::new(640, 510, 630, 500),
+            Rect::new(1280, 0, 630, 500),
+            Rect::new(1280, 510, 630, 500),
+        ]);
+    }
+
+    #[test]
+    fn seven_clients_give_the_rightmost_column_an_extra_row() {
+        assert_eq!(Grid.arrange(Rect::new(0, 0, 1910, 1010), &clients(7), &workspace(10)), vec![
+            Rect::new(0, 0, 630, 500),
+            Rect::new(0, 510, 630, 500),
+            Rect::new(640, 0, 630, 500),
+            Rect::new(640, 510, 630, 500),
+            Rect::new(1280, 0, 630, 330),
+            Rect::new(1280, 340, 630, 330),
+            Rect::new(1280, 680, 630, 330),
+        ]);
+    }
+}
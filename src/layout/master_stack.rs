@@ -0,0 +1,29 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+pub struct MasterStack;
+
+impl Layout for MasterStack {
+    fn name(&self) -> &str {
+        "master-stack"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect> {
+        let gap = workspace.inner_gap;
+        let masters = workspace.master_count.min(clients.len());
+
+        if masters == 0 || masters == clients.len() {
+            return area.split_rows(clients.len(), gap);
+        }
+
+        let master_width = (area.width.saturating_sub(gap) as f32 * workspace.master_ratio) as u32;
+
+        let master = Rect::new(area.x, area.y, master_width, area.height);
+        let stack = Rect::new(area.x + (master_width + gap) as i32, area.y, area.width.saturating_sub(master_width + gap), area.height);
+
+        let mut rects = master.split_rows(masters, gap);
+        rects.extend(stack.split_rows(clients.len() - masters, gap));
+
+        rects
+    }
+}
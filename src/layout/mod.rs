@@ -0,0 +1,136 @@
+mod master_stack;
+mod monocle;
+mod grid;
+mod bottom_stack;
+mod dwindle;
+mod bsp;
+mod centered_master;
+
+use crate::wm::{Client, Workspace};
+
+pub use master_stack::MasterStack;
+pub use monocle::Monocle;
+pub use grid::Grid;
+pub use bottom_stack::BottomStack;
+pub use dwindle::Dwindle;
+pub use bsp::{Bsp, Node, Split};
+pub use centered_master::CenteredMaster;
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /*
+     * Splits the rectangle into `count` rows of equal height with `gap` pixels between them
+    */
+    pub fn split_rows(&self, count: usize, gap: u32) -> Vec<Rect> {
+        let height = (self.height + gap) / count.max(1) as u32;
+
+        (0..count as u32)
+            .map(|index| Rect::new(self.x, self.y + (height * index) as i32, self.width, height.saturating_sub(gap)))
+            .collect::<Vec<Rect>>()
+    }
+
+    /*
+     * Splits the rectangle into `count` columns of equal width with `gap` pixels between them
+    */
+    pub fn split_columns(&self, count: usize, gap: u32) -> Vec<Rect> {
+        let width = (self.width + gap) / count.max(1) as u32;
+
+        (0..count as u32)
+            .map(|index| Rect::new(self.x + (width * index) as i32, self.y, width.saturating_sub(gap), self.height))
+            .collect::<Vec<Rect>>()
+    }
+}
+
+/*
+ * A layout receives the usable area of the monitor and the tiled clients of the workspace,
+ * it returns one rectangle per client in the same order as the clients were given
+*/
+pub trait Layout {
+    fn name(&self) -> &str;
+
+    fn arrange(&self, area: Rect, clients: &[Client], workspace: &Workspace) -> Vec<Rect>;
+
+    /*
+     * Layouts where the tiled clients overlap want the focused client raised above the others
+    */
+    fn raise_focused(&self) -> bool {
+        false
+    }
+}
+
+pub fn layouts() -> Vec<Box<dyn Layout>> {
+    vec![
+        Box::new(MasterStack),
+        Box::new(Monocle),
+        Box::new(Grid),
+        Box::new(BottomStack),
+        Box::new(Dwindle),
+        Box::new(Bsp),
+        Box::new(CenteredMaster),
+    ]
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::config::Padding;
+    use crate::layout::{layouts, Rect, Split};
+    use crate::wm::{Client, Workspace};
+
+    pub fn workspace(inner_gap: u32) -> Workspace {
+        Workspace {
+            name: String::from("1"),
+            clients: Vec::new(),
+            padding: Padding::default(),
+            master_count: 1,
+            master_ratio: 0.5,
+            inner_gap,
+            outer_gap: 0,
+            layout: 0,
+            tree: None,
+            presel: Split::Horizontal,
+        }
+    }
+
+    pub fn clients(count: u64) -> Vec<Client> {
+        (1..=count).map(|window| Client::new(window, true, 1)).collect()
+    }
+
+    #[test]
+    fn gaps_larger_than_the_area_do_not_underflow() {
+        let area = Rect::new(0, 0, 40, 30);
+
+        for layout in layouts() {
+            for count in 1..=8 {
+                let mut workspace = workspace(100);
+
+                workspace.master_count = 2;
+
+                for client in clients(count) {
+                    workspace.insert_node(client.window - 1, client.window);
+                }
+
+                let rects = layout.arrange(area, &clients(count), &workspace);
+
+                assert_eq!(rects.len(), count as usize, "{}", layout.name());
+                assert!(rects.iter().all(|rect| rect.width <= area.width && rect.height <= area.height), "{}", layout.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::layout::{Layout, Rect};
+use crate::wm::{Client, Workspace};
+
+pub struct Monocle;
+
+impl Layout for Monocle {
+    fn name(&self) -> &str {
+        "monocle"
+    }
+
+    fn arrange(&self, area: Rect, clients: &[Client], _workspace: &Workspace) -> Vec<Rect> {
+        vec![area; clients.len()]
+    }
+
+    fn raise_focused(&self) -> bool {
+        true
+    }
+}
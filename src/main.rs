@@ -1,4 +1,5 @@
 mod config;
+mod layout;
 mod xlib;
 mod wm;
 
@@ -1,4 +1,5 @@
-use crate::config::{Action, Internal};
+use crate::config::{Action, Internal, MonitorTarget, Padding};
+use crate::layout::{self, Layout, Node, Rect, Split};
 use crate::Config;
 use crate::xlib;
 
@@ -9,23 +10,147 @@ use std::ffi::{CStr, CString};
 use std::process::Command;
 use std::ptr;
 use std::env;
+use std::mem;
 
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Client {
-    window: u64,
-    tiled: bool,
+    pub window: u64,
+    pub tiled: bool,
+    pub sticky: bool,
+    pub tags: u32,
 }
 
 impl Client {
-    pub fn new(window: u64, tiled: bool) -> Client {
+    pub fn new(window: u64, tiled: bool, tags: u32) -> Client {
         Client {
             window,
-            tiled
+            tiled,
+            sticky: false,
+            tags,
         }
     }
 }
 
+/*
+ * Workspaces past the 32nd have no tag
+*/
+pub fn tag(workspace: usize) -> u32 {
+    1u32.checked_shl(workspace as u32).unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub name: String,
+    pub clients: Vec<Client>,
+    pub padding: Padding,
+    pub master_count: usize,
+    pub master_ratio: f32,
+    pub inner_gap: u32,
+    pub outer_gap: u32,
+    pub layout: usize,
+    pub tree: Option<Node>,
+    pub presel: Split,
+}
+
+impl Workspace {
+    pub fn new(config: &Config, monitor: &str, index: usize) -> Workspace {
+        let workspace_config = config.workspace_config(monitor, index);
+
+        Workspace {
+            name: config.workspace_name(monitor, index),
+            clients: Vec::new(),
+            padding: workspace_config.padding,
+            master_count: workspace_config.master_count,
+            master_ratio: workspace_config.master_ratio,
+            inner_gap: workspace_config.inner_gap,
+            outer_gap: workspace_config.outer_gap,
+            layout: layout::layouts().iter().position(|l| l.name() == workspace_config.default_layout).unwrap_or_default(),
+            tree: None,
+            presel: Split::Horizontal,
+        }
+    }
+
+    /*
+     * Builds the workspaces named in the config for a monitor, workspaces that existed before a restart keep their clients,
+     * and tree while the layout, padding, gaps and master area are taken from the new config.
+     * Clients of workspaces that were removed from the config are moved to the last workspace
+    */
+    pub fn load(config: &Config, monitor: &str, old_workspaces: &[Workspace]) -> Vec<Workspace> {
+        let count = config.workspace_names(monitor).len().max(1);
+
+        let mut workspaces = (0..count)
+            .map(|index| {
+                let workspace = Workspace::new(config, monitor, index);
+
+                match old_workspaces.get(index) {
+                    Some(old) => Workspace {
+                        clients: old.clients.clone(),
+                        tree: old.tree.clone(),
+                        presel: old.presel,
+                        ..workspace
+                    },
+                    None => workspace,
+                }
+            })
+            .collect::<Vec<Workspace>>();
+
+        if let Some(last) = workspaces.last_mut() {
+            for workspace in old_workspaces.iter().skip(count) {
+                last.clients.extend(workspace.clients.iter().copied());
+            }
+        }
+
+        workspaces
+    }
+
+    /*
+     * Splits the node of `target` in the preselected direction,
+     * falls back to the last node when `target` is not part of the tree
+    */
+    pub fn insert_node(&mut self, target: u64, window: u64) {
+        match &mut self.tree {
+            Some(tree) if tree.contains(window) => {},
+            Some(tree) => {
+                if !tree.insert(target, window, self.presel) {
+                    let last = tree.windows().last().copied().unwrap_or_default();
+
+                    tree.insert(last, window, self.presel);
+                }
+            },
+            None => self.tree = Some(Node::Leaf(window)),
+        }
+    }
+
+    pub fn remove_node(&mut self, window: u64) {
+        self.tree = self.tree.take().and_then(|tree| tree.remove(window));
+    }
+
+    /*
+     * Clients can become tiled or leave the workspace without going through MapRequest or UnmapNotify,
+     * this makes sure the tree holds exactly the tiled clients
+    */
+    pub fn sync_tree(&mut self, focused: u64) {
+        let tiled = self.clients.iter()
+            .filter(|c| c.tiled)
+            .map(|c| c.window)
+            .collect::<Vec<u64>>();
+
+        for window in self.tree.as_ref().map(|tree| tree.windows()).unwrap_or_default() {
+            if !tiled.contains(&window) {
+                self.remove_node(window);
+            }
+        }
+
+        for window in tiled {
+            self.insert_node(focused, window);
+        }
+    }
+}
+
+pub const BAR_MARGIN: i32 = 10;
+pub const BAR_HEIGHT: u32 = 30;
+
 #[derive(Debug)]
 pub struct Bar {
     pub window: u64,
@@ -38,16 +163,34 @@ pub struct Bar {
 
 #[derive(Debug)]
 pub struct Monitor {
+    pub name: String,
     pub x: i32,
     pub y: i32,
     pub width: u32,
     pub height: u32,
-    pub clients: [Vec<Client>; 4],
+    pub workspaces: Vec<Workspace>,
     pub fullscreen: Option<Client>,
     pub workspace: usize,
+    pub last_workspace: usize,
+    pub view: u32,
+    pub strut: Padding,
+    pub workarea: Rect,
     pub bar: Option<Bar>,
 }
 
+impl Monitor {
+    /*
+     * Monitors are matched by the name of their output, only unnamed monitors fall back to their index
+    */
+    pub fn find(monitors: &[Monitor], name: &str, index: usize) -> Option<usize> {
+        if name.is_empty() {
+            monitors.get(index).filter(|monitor| monitor.name.is_empty()).map(|_| index)
+        } else {
+            monitors.iter().position(|monitor| monitor.name == name)
+        }
+    }
+}
+
 pub struct FloatClient {
     start: Option<x11::xlib::XButtonEvent>,
     attr: Option<x11::xlib::XWindowAttributes>,
@@ -57,8 +200,12 @@ pub struct WindowManager {
     pub display: xlib::Display,
     config: Config,
     monitors: Vec<Monitor>,
+    layouts: Vec<Box<dyn Layout>>,
     float_client: FloatClient,
     window: u64,
+    monitor: usize,
+    docks: Vec<u64>,
+    strut: Padding,
 }
 
 impl WindowManager {
@@ -66,7 +213,7 @@ impl WindowManager {
         let mut display = xlib::Display::open(ptr::null())?;
         let config = Config::load()?;
         let window = display.root;
-        let monitors = display.get_monitors(config.bar, &Vec::new())?;
+        let monitors = display.get_monitors(&config, &Vec::new())?;
 
         display.set_net_supported(display.root);
         display.set_desktop_viewport(display.root);
@@ -75,11 +222,15 @@ impl WindowManager {
             display,
             config,
             monitors,
+            layouts: layout::layouts(),
             float_client: FloatClient {
                 start: None,
                 attr: None,
             },
             window,
+            monitor: 0,
+            docks: Vec::new(),
+            strut: Padding::default(),
         })
     }
 
@@ -94,14 +245,7 @@ impl WindowManager {
             signal::sigaction(signal::Signal::SIGCHLD, &sa)?;
         }
 
-        let keys = [
-            x11::keysym::XK_1,
-            x11::keysym::XK_2,
-            x11::keysym::XK_3,
-            x11::keysym::XK_4,
-        ];
-
-        for key in keys {
+        for key in (x11::keysym::XK_1..=x11::keysym::XK_9).take(self.workspace_keys()) {
             self.display.grab_key(key, xlib::Mod4Mask, self.display.root);
         }
 
@@ -113,9 +257,277 @@ impl WindowManager {
         self.display.grab_button(xlib::Button3, self.display.root);
 
         self.display.select_input(self.display.root);
+        self.display.select_screen_change_input();
         self.display.set_wm_name(self.display.root, "ZovaWM");
 
-        self.display.set_property_u64("_NET_NUMBER_OF_DESKTOPS", self.monitors[0].clients.len() as u64, xlib::XA_CARDINAL)?;
+        for monitor in 0..self.monitors.len() {
+            self.prune_workspaces(monitor)?;
+        }
+
+        self.update_struts();
+        self.set_desktops()?;
+
+        Ok(())
+    }
+
+    /*
+     * The desktop properties on the root window can only describe one monitor, monitors can have
+     * a different number of workspaces so the count and names describe the first monitor while the
+     * current desktop follows the focused monitor, clamped to the advertised count
+    */
+    fn set_desktops(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = 0;
+        let focused = self.current_monitor();
+        let current = self.monitors[focused].workspace.min(self.monitors[monitor].workspaces.len().saturating_sub(1));
+        let workarea = [
+            self.strut.left as u64,
+            self.strut.top as u64,
+            (self.display.display_width() as i32 - self.strut.left - self.strut.right).max(0) as u64,
+            (self.display.display_height() as i32 - self.strut.top - self.strut.bottom).max(0) as u64,
+        ];
+
+        self.display.set_property_u64("_NET_NUMBER_OF_DESKTOPS", self.monitors[monitor].workspaces.len() as u64, xlib::XA_CARDINAL)?;
+        self.display.set_workarea(self.display.root, &workarea.repeat(self.monitors[monitor].workspaces.len()));
+        self.display.set_property_u64("_NET_CURRENT_DESKTOP", current as u64, xlib::XA_CARDINAL)?;
+        self.display.set_desktop_names(
+            self.display.root,
+            &self.monitors[monitor].workspaces.iter()
+                .map(|workspace| workspace.name.clone())
+                .collect::<Vec<String>>()
+        );
+
+        Ok(())
+    }
+
+    /*
+     * Struts are relative to the edges of the root window, they are reserved on the monitor the dock is on.
+     * _NET_WM_STRUT_PARTIAL starts with the same four values as _NET_WM_STRUT
+    */
+    fn update_struts(&mut self) {
+        let root_width = self.display.display_width() as i32;
+        let root_height = self.display.display_height() as i32;
+
+        self.strut = Padding::default();
+
+        for monitor in &mut self.monitors {
+            monitor.strut = Padding::default();
+        }
+
+        for dock in self.docks.clone() {
+            let mut strut = self.display.get_cardinals(dock, "_NET_WM_STRUT_PARTIAL");
+
+            if strut.len() < 4 {
+                strut = self.display.get_cardinals(dock, "_NET_WM_STRUT");
+            }
+
+            if strut.len() < 4 {
+                continue;
+            }
+
+            let (left, right, top, bottom) = (strut[0] as i32, strut[1] as i32, strut[2] as i32, strut[3] as i32);
+            let attr = self.display.get_window_attributes(dock);
+
+            if let Some(monitor) = self.monitor_at(attr.x + attr.width / 2, attr.y + attr.height / 2) {
+                let monitor = &mut self.monitors[monitor];
+
+                monitor.strut.left = monitor.strut.left.max(left - monitor.x);
+                monitor.strut.right = monitor.strut.right.max(right - (root_width - monitor.x - monitor.width as i32));
+                monitor.strut.top = monitor.strut.top.max(top - monitor.y);
+                monitor.strut.bottom = monitor.strut.bottom.max(bottom - (root_height - monitor.y - monitor.height as i32));
+            }
+
+            self.strut.left = self.strut.left.max(left);
+            self.strut.right = self.strut.right.max(right);
+            self.strut.top = self.strut.top.max(top);
+            self.strut.bottom = self.strut.bottom.max(bottom);
+        }
+
+        self.update_workareas();
+    }
+
+    /*
+     * The work area is what is left of a monitor after the struts and the default bar,
+     * the bar is placed at the top of the space the struts leave free
+    */
+    fn update_workareas(&mut self) {
+        for monitor in &mut self.monitors {
+            let mut workarea = Rect::new(
+                monitor.x + monitor.strut.left,
+                monitor.y + monitor.strut.top,
+                (monitor.width as i32 - monitor.strut.left - monitor.strut.right).max(0) as u32,
+                (monitor.height as i32 - monitor.strut.top - monitor.strut.bottom).max(0) as u32
+            );
+
+            if let Some(bar) = &monitor.bar {
+                let reserved = BAR_MARGIN as u32 + BAR_HEIGHT;
+
+                self.display.resize_window(
+                    bar.window,
+                    workarea.x + BAR_MARGIN,
+                    workarea.y + BAR_MARGIN,
+                    workarea.width.saturating_sub(BAR_MARGIN as u32 * 2),
+                    BAR_HEIGHT
+                );
+
+                workarea.y += reserved as i32;
+                workarea.height = workarea.height.saturating_sub(reserved);
+            }
+
+            monitor.workarea = workarea;
+        }
+    }
+
+    /*
+     * The number keys can only select the first nine workspaces,
+     * with dynamic workspaces all of them are grabbed as they can create new workspaces
+    */
+    fn workspace_keys(&self) -> usize {
+        if self.config.dynamic_workspaces {
+            9
+        } else {
+            self.monitors.iter()
+                .map(|monitor| monitor.workspaces.len())
+                .max()
+                .unwrap_or_default()
+        }
+    }
+
+    /*
+     * With dynamic workspaces a workspace past the last one is created on demand
+    */
+    fn get_workspace(&mut self, monitor: usize, workspace: usize) -> Option<usize> {
+        let workspaces = self.monitors[monitor].workspaces.len();
+
+        if workspace < workspaces {
+            Some(workspace)
+        } else if self.config.dynamic_workspaces {
+            let workspace = Workspace::new(&self.config, &self.monitors[monitor].name, workspaces);
+
+            self.monitors[monitor].workspaces.push(workspace);
+
+            Some(workspaces)
+        } else {
+            None
+        }
+    }
+
+    /*
+     * With dynamic workspaces every empty workspace that is not being viewed is removed,
+     * the remaining workspaces are renamed and renumbered to fill the gaps
+    */
+    fn prune_workspaces(&mut self, monitor: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.config.dynamic_workspaces {
+            return Ok(());
+        }
+
+        let mut index = 0;
+        let mut pruned = false;
+
+        while index < self.monitors[monitor].workspaces.len() {
+            let monitor = &mut self.monitors[monitor];
+
+            if monitor.workspaces[index].clients.is_empty() && index != monitor.workspace {
+                monitor.workspaces.remove(index);
+
+                if monitor.last_workspace == index {
+                    monitor.last_workspace = monitor.workspace;
+                }
+
+                if monitor.workspace > index {
+                    monitor.workspace -= 1;
+                }
+
+                if monitor.last_workspace > index {
+                    monitor.last_workspace -= 1;
+                }
+
+                pruned = true;
+            } else {
+                index += 1;
+            }
+        }
+
+        if pruned {
+            let name = self.monitors[monitor].name.clone();
+
+            for (index, workspace) in self.monitors[monitor].workspaces.iter_mut().enumerate() {
+                workspace.name = self.config.workspace_name(&name, index);
+
+                for client in &workspace.clients {
+                    let desktop = if client.sticky { 0xffffffff } else { index as u64 };
+
+                    self.display.set_window_property_u64(client.window, "_NET_WM_DESKTOP", desktop, xlib::XA_CARDINAL)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Rebuilds the monitors after the screen changed, monitors that still exist keep their workspaces
+     * and the clients of monitors that were removed are moved to the first monitor
+    */
+    fn update_monitors(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let monitors = self.display.get_monitors(&self.config, &self.monitors)?;
+
+        /*
+         * outputs can briefly disappear while the screen is being reconfigured
+        */
+        if monitors.is_empty() {
+            println!("[+] screen changed: no monitors, keeping the current ones");
+
+            return Ok(());
+        }
+
+        self.cleanup_bar();
+
+        let mut old_monitors = mem::replace(&mut self.monitors, monitors);
+
+        let matches = self.monitors.iter().enumerate()
+            .map(|(index, monitor)| Monitor::find(&old_monitors, &monitor.name, index))
+            .collect::<Vec<Option<usize>>>();
+
+        for (index, old) in matches.iter().enumerate() {
+            if let Some(old) = old {
+                let old = &mut old_monitors[*old];
+                let monitor = &mut self.monitors[index];
+                let (x, y) = (monitor.x - old.x, monitor.y - old.y);
+
+                monitor.workspaces = mem::take(&mut old.workspaces);
+                monitor.workspace = old.workspace;
+                monitor.last_workspace = old.last_workspace;
+                monitor.view = old.view;
+                monitor.fullscreen = old.fullscreen;
+
+                for client in monitor.workspaces.iter().flat_map(|workspace| workspace.clients.clone()).collect::<Vec<Client>>() {
+                    self.shift_floating(client, x, y);
+                }
+            }
+        }
+
+        for old in old_monitors.iter().filter(|old| !old.workspaces.is_empty()) {
+            let (x, y) = (self.monitors[0].x - old.x, self.monitors[0].y - old.y);
+
+            for (index, workspace) in old.workspaces.iter().enumerate() {
+                for client in &workspace.clients {
+                    self.shift_floating(*client, x, y);
+
+                    let target = if client.sticky { self.monitors[0].workspace } else { index.min(self.monitors[0].workspaces.len() - 1) };
+
+                    self.monitors[0].workspaces[target].clients.push(*client);
+                }
+            }
+        }
+
+        self.monitor = matches.iter().position(|old| *old == Some(self.monitor)).unwrap_or_default();
+        self.update_struts();
+
+        for monitor in 0..self.monitors.len() {
+            self.show_workspace(monitor)?;
+        }
+
+        println!("[+] screen changed: {} monitors", self.monitors.len());
 
         Ok(())
     }
@@ -156,6 +568,7 @@ impl WindowManager {
         for monitor in &mut self.monitors {
             if let Some(bar) = &mut monitor.bar {
                 self.display.xft_free(bar);
+                self.display.destroy_window(bar.window);
             }
         }
     }
@@ -166,19 +579,26 @@ impl WindowManager {
                 self.display.map_window(bar.window);
                 self.display.clear_window(bar.window);
 
-                for workspace in 0..monitor.clients.len() {
-                    if workspace == monitor.workspace {
-                        self.display.draw_rec((workspace as i32 * 25) + 5, 5, 20, 20, 0x5ec587, bar.window, bar.gc);
+                let mut x = 5;
+
+                for (index, workspace) in monitor.workspaces.iter().enumerate() {
+                    let text_width = self.display.xft_measure_string(&workspace.name, bar.font).width as i32;
+                    let width = (text_width + 12).max(20);
 
-                        self.display.xft_draw_string(&format!("{}", workspace + 1), (workspace as i32 * 25) + 11, 20, bar.font, &bar.bg, bar.draw);
+                    if index == monitor.workspace || (self.config.tags && monitor.view & tag(index) != 0) {
+                        self.display.draw_rec(x, 5, width as u32, 20, 0x5ec587, bar.window, bar.gc);
+
+                        self.display.xft_draw_string(&workspace.name, x + (width - text_width) / 2, 20, bar.font, &bar.bg, bar.draw);
                     } else {
-                        self.display.xft_draw_string(&format!("{}", workspace + 1), (workspace as i32 * 25) + 11, 20, bar.font, &bar.fg, bar.draw);
+                        self.display.xft_draw_string(&workspace.name, x + (width - text_width) / 2, 20, bar.font, &bar.fg, bar.draw);
                     }
+
+                    x += width + 5;
                 }
 
                 self.display.xft_draw_string(
                     "ZovaWM",
-                    (self.monitors[0].width as i32 / 2) - (self.display.xft_measure_string("ZovaWM", bar.font).width as i32 / 2),
+                    (monitor.workarea.width as i32 / 2 - BAR_MARGIN) - (self.display.xft_measure_string("ZovaWM", bar.font).width as i32 / 2),
                     20,
                     bar.font,
                     &bar.fg,
@@ -191,10 +611,16 @@ impl WindowManager {
     }
 
     fn tile_clients(&mut self) {
+        for monitor in &mut self.monitors {
+            monitor.workspaces[monitor.workspace].sync_tree(self.window);
+        }
+
         for monitor in &self.monitors {
-            let clients = monitor.clients[monitor.workspace].iter()
+            let workspace = &monitor.workspaces[monitor.workspace];
+            let clients = workspace.clients.iter()
                 .filter(|x| x.tiled)
-                .collect::<Vec<&Client>>();
+                .copied()
+                .collect::<Vec<Client>>();
 
             if let Some(client) = monitor.fullscreen {
                 self.display.resize_window(
@@ -204,56 +630,105 @@ impl WindowManager {
                     monitor.width,
                     monitor.height
                 );
-            } else if clients.len() == 1 {
-                self.display.resize_window(
-                    clients[0].window,
-                    monitor.x + self.config.padding.right,
-                    self.config.padding.top,
-                    monitor.width - self.config.padding.right as u32 - self.config.padding.left as u32,
-                    monitor.height - self.config.padding.bottom as u32 - self.config.padding.top as u32
-                );
-            } else if !clients.is_empty() {
-                self.display.resize_window(
-                    clients[0].window,
-                    monitor.x + self.config.padding.right,
-                    self.config.padding.top,
-                    (monitor.width / 2) - self.config.padding.right as u32 - 5,
-                    monitor.height - self.config.padding.bottom as u32 - self.config.padding.top as u32
+            } else {
+                let outer_gap = if self.config.smart_gaps && clients.len() == 1 { 0 } else { workspace.outer_gap };
+
+                let padding = workspace.padding;
+
+                /*
+                 * large paddings or gaps shrink the area to nothing instead of wrapping around
+                */
+                let area = Rect::new(
+                    monitor.workarea.x + padding.left + outer_gap as i32,
+                    monitor.workarea.y + padding.top + outer_gap as i32,
+                    monitor.workarea.width.saturating_sub(padding.left.max(0) as u32 + padding.right.max(0) as u32 + outer_gap * 2),
+                    monitor.workarea.height.saturating_sub(padding.top.max(0) as u32 + padding.bottom.max(0) as u32 + outer_gap * 2)
                 );
 
-                for (index, client) in clients[1..].iter().enumerate() {
-                    self.display.resize_window(
-                        client.window,
-                        monitor.x + (monitor.width as i32 / 2) + 5,
-                        (
-                            (monitor.height as i32 - self.config.padding.top - self.config.padding.bottom + 10)
-                                / (clients.len() as i32 - 1)
-                        ) * index as i32 + self.config.padding.top,
-                        (monitor.width as u32 / 2) - self.config.padding.left as u32 - 5,
-                        (monitor.height as u32 - self.config.padding.top as u32 - self.config.padding.bottom as u32 + 10)
-                            / (clients.len() as u32 - 1) - 10,
-                    );
+                for (client, rect) in clients.iter().zip(self.layouts[workspace.layout].arrange(area, &clients, workspace)) {
+                    self.display.resize_window(client.window, rect.x, rect.y, rect.width, rect.height);
+                }
+
+                if self.layouts[workspace.layout].raise_focused() && clients.iter().any(|c| c.window == self.window) {
+                    self.display.raise_window(self.window);
                 }
             }
         }
     }
 
+    /*
+     * The focused monitor follows the focused client, with `focus-follows-pointer-monitor` it follows the pointer instead
+    */
     fn current_monitor(&mut self) -> usize {
-        let pointer = self.display.query_pointer();
+        if self.config.focus_follows_pointer_monitor {
+            let pointer = self.display.query_pointer();
 
-        for (index, monitor) in self.monitors.iter().enumerate() {
-            if (monitor.x..monitor.x + monitor.width as i32).contains(&pointer.x) {
-                return index;
+            if let Some(monitor) = self.monitor_at(pointer.x, pointer.y) {
+                self.monitor = monitor;
             }
         }
 
-        0
+        self.monitor.min(self.monitors.len() - 1)
+    }
+
+    fn monitor_at(&self, x: i32, y: i32) -> Option<usize> {
+        self.monitors.iter().position(|monitor| {
+            (monitor.x..monitor.x + monitor.width as i32).contains(&x) && (monitor.y..monitor.y + monitor.height as i32).contains(&y)
+        })
+    }
+
+    fn window_monitor(&self, window: u64) -> Option<usize> {
+        self.monitors.iter().position(|monitor| {
+            monitor.workspaces[monitor.workspace].clients.iter().any(|c| c.window == window)
+        })
+    }
+
+    fn target_monitor(&mut self, target: MonitorTarget) -> Option<usize> {
+        let monitor = self.current_monitor();
+        let monitors = self.monitors.len();
+
+        match target {
+            MonitorTarget::Next => Some((monitor + 1) % monitors),
+            MonitorTarget::Prev => Some((monitor + monitors - 1) % monitors),
+            MonitorTarget::Index(index) => (index < monitors).then_some(index),
+        }
+    }
+
+    /*
+     * Floating windows keep their position relative to the monitor they are moved to
+    */
+    fn move_floating(&mut self, client: Client, from: usize, to: usize) {
+        self.shift_floating(client, self.monitors[to].x - self.monitors[from].x, self.monitors[to].y - self.monitors[from].y);
+    }
+
+    fn shift_floating(&mut self, client: Client, x: i32, y: i32) {
+        if !client.tiled && (x != 0 || y != 0) {
+            let attr = self.display.get_window_attributes(client.window);
+
+            self.display.resize_window(client.window, attr.x + x, attr.y + y, attr.width as u32, attr.height as u32);
+        }
+    }
+
+    fn current_layout(&mut self) -> &dyn Layout {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        self.layouts[self.monitors[monitor].workspaces[workspace].layout].as_ref()
+    }
+
+    fn set_layout(&mut self, layout: usize) {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        self.monitors[monitor].workspaces[workspace].layout = layout;
+
+        self.tile_clients();
     }
 
     fn window_to_client_index(&mut self, window: u64) -> Option<usize> {
         let monitor = self.current_monitor();
 
-        for (index, client) in self.monitors[monitor].clients[self.monitors[monitor].workspace].iter().enumerate() {
+        for (index, client) in self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients.iter().enumerate() {
             if client.window == window {
                 return Some(index);
             }
@@ -263,7 +738,7 @@ impl WindowManager {
     }
 
     fn is_tiled(&mut self, window: u64, monitor: usize, workspace: usize) -> bool {
-        !self.monitors[monitor].clients[workspace].iter()
+        !self.monitors[monitor].workspaces[workspace].clients.iter()
             .filter(|c| c.tiled && c.window == window)
             .collect::<Vec<&Client>>()
             .is_empty()
@@ -272,32 +747,388 @@ impl WindowManager {
     fn goto_workspace(&mut self, workspace: usize) -> Result<(), Box<dyn std::error::Error>> {
         let monitor = self.current_monitor();
 
-        if workspace < self.monitors[monitor].clients.len() {
-            self.monitors[monitor].workspace = workspace;
-            self.display.set_property_u64("_NET_CURRENT_DESKTOP", self.monitors[monitor].workspace as u64, xlib::XA_CARDINAL)?;
+        if let Some(workspace) = self.get_workspace(monitor, workspace) {
+            let current = self.monitors[monitor].workspace;
+
+            if workspace != current {
+                self.monitors[monitor].last_workspace = current;
 
-            for client in self.monitors[monitor].clients[self.monitors[monitor].workspace].clone() {
-                self.display.map_window(client.window);
+                /*
+                 * sticky clients follow the monitor to the workspace it switches to
+                */
+                let sticky = self.take_sticky(monitor);
 
-                if !client.tiled {
-                    self.display.raise_window(client.window);
+                if self.config.shared_workspaces {
+                    self.share_workspace(monitor, workspace)?;
                 }
+
+                self.monitors[monitor].workspaces[workspace].clients.extend(sticky);
             }
 
-            for (workspace, clients) in self.monitors[monitor].clients.iter().enumerate() {
-                if workspace != self.monitors[monitor].workspace {
-                    for client in clients {
-                        self.display.unmap_window(client.window);
+            self.monitors[monitor].workspace = workspace;
+            self.monitors[monitor].view = tag(workspace);
+
+            self.show_workspace(monitor)?;
+        }
+
+        Ok(())
+    }
+
+    fn take_sticky(&mut self, monitor: usize) -> Vec<Client> {
+        let workspace = self.monitors[monitor].workspace;
+        let workspace = &mut self.monitors[monitor].workspaces[workspace];
+
+        let (sticky, clients) = workspace.clients.iter()
+            .partition::<Vec<Client>, _>(|c| c.sticky);
+
+        for client in &sticky {
+            workspace.remove_node(client.window);
+        }
+
+        workspace.clients = clients;
+
+        sticky
+    }
+
+    /*
+     * With shared workspaces a workspace belongs to no monitor in particular, when it is visible on another monitor
+     * the two monitors swap workspaces, otherwise its clients are pulled over from the other monitors
+    */
+    fn share_workspace(&mut self, monitor: usize, workspace: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let current = self.monitors[monitor].workspace;
+
+        for other in (0..self.monitors.len()).filter(|other| *other != monitor) {
+            if self.monitors[other].workspace == workspace && current < self.monitors[other].workspaces.len() {
+                let sticky = self.take_sticky(other);
+
+                for index in [current, workspace] {
+                    let theirs = self.monitors[other].workspaces[index].clone();
+                    let ours = mem::replace(&mut self.monitors[monitor].workspaces[index], theirs);
+
+                    self.monitors[other].workspaces[index] = ours;
+                }
+
+                for index in [current, workspace] {
+                    for client in self.monitors[monitor].workspaces[index].clients.clone() {
+                        self.move_floating(client, other, monitor);
+                    }
+
+                    for client in self.monitors[other].workspaces[index].clients.clone() {
+                        self.move_floating(client, monitor, other);
                     }
                 }
+
+                let fullscreen = self.monitors[monitor].fullscreen.take();
+
+                self.monitors[monitor].fullscreen = mem::replace(&mut self.monitors[other].fullscreen, fullscreen);
+
+                self.monitors[other].last_workspace = workspace;
+                self.monitors[other].workspace = current;
+                self.monitors[other].view = tag(current);
+                self.monitors[other].workspaces[current].clients.extend(sticky);
+
+                self.show_workspace(other)?;
+            } else if let Some(shared) = self.monitors[other].workspaces.get_mut(workspace) {
+                let clients = mem::take(&mut shared.clients);
+
+                shared.tree = None;
+
+                for client in &clients {
+                    self.move_floating(*client, other, monitor);
+                }
+
+                self.monitors[monitor].workspaces[workspace].clients.extend(clients);
+            }
+        }
+
+        Ok(())
+    }
+
+    /*
+     * In tag mode every client with a tag in the view is gathered in the current workspace,
+     * the other clients are returned to the workspace of their lowest tag
+    */
+    fn gather_tags(&mut self, monitor: usize) {
+        let monitor = &mut self.monitors[monitor];
+        let current = monitor.workspace;
+
+        let mut clients = mem::take(&mut monitor.workspaces[current].clients);
+
+        for workspace in monitor.workspaces.iter_mut() {
+            clients.append(&mut workspace.clients);
+        }
+
+        for client in clients {
+            let workspace = if client.sticky || client.tags & monitor.view != 0 {
+                current
+            } else {
+                (client.tags.trailing_zeros() as usize).min(monitor.workspaces.len() - 1)
+            };
+
+            monitor.workspaces[workspace].clients.push(client);
+        }
+    }
+
+    fn show_workspace(&mut self, monitor: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if self.config.tags {
+            self.gather_tags(monitor);
+        }
+
+        for client in self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients.clone() {
+            self.display.map_window(client.window);
+
+            if !client.tiled {
+                self.display.raise_window(client.window);
+            }
+        }
+
+        for (index, workspace) in self.monitors[monitor].workspaces.iter().enumerate() {
+            if index != self.monitors[monitor].workspace {
+                for client in &workspace.clients {
+                    self.display.unmap_window(client.window);
+                }
+            }
+        }
+
+        self.prune_workspaces(monitor)?;
+        self.set_desktops()?;
+
+        self.tile_clients();
+
+        Ok(())
+    }
+
+    fn toggle_view(&mut self, workspace: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+        let current = self.monitors[monitor].workspace;
+        let view = self.monitors[monitor].view ^ tag(workspace);
+
+        if workspace < self.monitors[monitor].workspaces.len() && view != 0 {
+            self.monitors[monitor].view = view;
+
+            if view & tag(current) == 0 {
+                self.monitors[monitor].workspace = view.trailing_zeros() as usize;
+                self.monitors[monitor].last_workspace = current;
+            }
+
+            self.show_workspace(monitor)?;
+        }
+
+        Ok(())
+    }
+
+    fn toggle_tag(&mut self, window: u64, workspace: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+        let current = self.monitors[monitor].workspace;
+
+        if workspace < self.monitors[monitor].workspaces.len() {
+            if let Some(index) = self.window_to_client_index(window) {
+                let client = &mut self.monitors[monitor].workspaces[current].clients[index];
+
+                if client.tags ^ tag(workspace) != 0 {
+                    client.tags ^= tag(workspace);
+
+                    self.show_workspace(monitor)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn move_to_workspace(&mut self, window: u64, workspace: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+
+        if self.transfer_to_workspace(window, workspace)?.is_some() {
+            self.show_workspace(monitor)?;
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Moves a client to another workspace without changing which windows are mapped, returns the workspace it was moved to.
+     * Following the client has to happen before the workspaces are shown again, unmapping a window that is shown
+     * right after would make the UnmapNotify handler forget about it.
+     * With shared workspaces a workspace that is visible on another monitor receives the client on that monitor.
+     * Returns None when the window is not a client of the current workspace
+    */
+    fn transfer_to_workspace(&mut self, window: u64, workspace: usize) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+        let current = self.monitors[monitor].workspace;
+
+        let Some(index) = self.window_to_client_index(window) else {
+            return Ok(None);
+        };
+
+        let Some(workspace) = self.get_workspace(monitor, workspace).filter(|workspace| *workspace != current) else {
+            return Ok(None);
+        };
+
+        let target = (0..self.monitors.len())
+            .find(|other| self.config.shared_workspaces && *other != monitor && self.monitors[*other].workspace == workspace)
+            .unwrap_or(monitor);
+
+        let client = self.monitors[monitor].workspaces[current].clients.remove(index);
+
+        if target != monitor {
+            self.move_floating(client, monitor, target);
+        }
+
+        self.monitors[monitor].workspaces[current].remove_node(window);
+        self.monitors[target].workspaces[workspace].clients.push(Client { tags: tag(workspace), ..client });
+
+        if self.monitors[monitor].fullscreen.is_some_and(|c| c.window == window) {
+            self.monitors[monitor].fullscreen = None;
+        }
+
+        self.display.set_window_property_u64(window, "_NET_WM_DESKTOP", workspace as u64, xlib::XA_CARDINAL)?;
+
+        Ok(Some(workspace))
+    }
+
+    /*
+     * Every client of the current workspace except the sticky ones is moved to the same workspace on the target monitor,
+     * which then switches to it
+    */
+    fn move_workspace_to_monitor(&mut self, target: MonitorTarget) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        let Some(target) = self.target_monitor(target).filter(|target| *target != monitor) else {
+            return Ok(());
+        };
+
+        let Some(target_workspace) = self.get_workspace(target, workspace) else {
+            return Ok(());
+        };
+
+        let sticky = self.take_sticky(monitor);
+        let empty = Workspace::new(&self.config, &self.monitors[monitor].name, workspace);
+        let moved = mem::replace(&mut self.monitors[monitor].workspaces[workspace], empty);
+
+        self.monitors[monitor].workspaces[workspace].clients.extend(sticky);
+
+        if self.monitors[monitor].fullscreen.is_some_and(|f| moved.clients.iter().any(|c| c.window == f.window)) {
+            self.monitors[target].fullscreen = self.monitors[monitor].fullscreen.take();
+        }
+
+        for client in &moved.clients {
+            self.move_floating(*client, monitor, target);
+            self.display.set_window_property_u64(client.window, "_NET_WM_DESKTOP", target_workspace as u64, xlib::XA_CARDINAL)?;
+        }
+
+        /*
+         * an empty workspace is replaced so the layout and gaps move along with the clients
+        */
+        let target_slot = &mut self.monitors[target].workspaces[target_workspace];
+
+        if target_slot.clients.is_empty() {
+            *target_slot = Workspace {
+                name: target_slot.name.clone(),
+                ..moved
+            };
+        } else {
+            target_slot.clients.extend(moved.clients);
+        }
+
+        let current = self.monitors[target].workspace;
+
+        if target_workspace != current {
+            let sticky = self.take_sticky(target);
+
+            self.monitors[target].last_workspace = current;
+            self.monitors[target].workspace = target_workspace;
+            self.monitors[target].view = tag(target_workspace);
+            self.monitors[target].workspaces[target_workspace].clients.extend(sticky);
+        }
+
+        self.show_workspace(target)?;
+        self.show_workspace(monitor)?;
+
+        Ok(())
+    }
+
+    /*
+     * The pointer is moved along to the center of the target monitor
+    */
+    fn focus_monitor(&mut self, target: MonitorTarget) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+
+        if let Some(target) = self.target_monitor(target).filter(|target| *target != monitor) {
+            let workspace = self.monitors[target].workspace;
+
+            self.monitor = target;
+
+            self.display.warp_pointer(
+                self.monitors[target].x + self.monitors[target].width as i32 / 2,
+                self.monitors[target].y + self.monitors[target].height as i32 / 2
+            );
+
+            if let Some(client) = self.monitors[target].workspaces[workspace].clients.first().copied() {
+                self.change_focus(client.window)?;
+            }
+
+            self.set_desktops()?;
+        }
+
+        Ok(())
+    }
+
+    fn send_to_monitor(&mut self, window: u64, target: MonitorTarget) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        if let Some(target) = self.target_monitor(target).filter(|target| *target != monitor) {
+            if let Some(index) = self.window_to_client_index(window) {
+                let client = self.monitors[monitor].workspaces[workspace].clients.remove(index);
+                let target_workspace = self.monitors[target].workspace;
+                let view = self.monitors[target].view;
+
+                self.monitors[monitor].workspaces[workspace].remove_node(window);
+
+                if self.monitors[monitor].fullscreen.is_some_and(|c| c.window == window) {
+                    self.monitors[monitor].fullscreen = None;
+                }
+
+                self.move_floating(client, monitor, target);
+                self.monitors[target].workspaces[target_workspace].clients.push(Client { tags: view, ..client });
+
+                let desktop = if client.sticky { 0xffffffff } else { target_workspace as u64 };
+
+                self.display.set_window_property_u64(window, "_NET_WM_DESKTOP", desktop, xlib::XA_CARDINAL)?;
+
+                self.show_workspace(target)?;
+                self.show_workspace(monitor)?;
             }
+        }
+
+        Ok(())
+    }
+
+    fn set_sticky(&mut self, window: u64, sticky: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        if let Some(index) = self.window_to_client_index(window) {
+            self.monitors[monitor].workspaces[workspace].clients[index].sticky = sticky;
+
+            let desktop = if sticky { 0xffffffff } else { workspace as u64 };
 
-            self.tile_clients();
+            self.display.set_window_property_u64(window, "_NET_WM_DESKTOP", desktop, xlib::XA_CARDINAL)?;
         }
 
         Ok(())
     }
 
+    fn is_sticky(&mut self, window: u64) -> bool {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        self.monitors[monitor].workspaces[workspace].clients.iter()
+            .any(|c| c.window == window && c.sticky)
+    }
+
     fn toggle_fullscreen(&mut self, window: u64) -> Result<(), Box<dyn std::error::Error>> {
         let monitor = self.current_monitor();
         let state_fullscreen = self.display.intern_atom("_NET_WM_STATE_FULLSCREEN");
@@ -306,7 +1137,7 @@ impl WindowManager {
             if let Some(client) = self.window_to_client_index(window) {
                 self.display.set_property_u64("_NET_WM_STATE", state_fullscreen, xlib::XA_ATOM)?;
 
-                self.monitors[monitor].fullscreen = Some(self.monitors[monitor].clients[self.monitors[monitor].workspace][client]);
+                self.monitors[monitor].fullscreen = Some(self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients[client]);
             }
         } else {
             self.display.set_property_null("_NET_WM_STATE", xlib::XA_ATOM)?;
@@ -323,17 +1154,92 @@ impl WindowManager {
         let monitor = self.current_monitor();
         let workspace = self.monitors[monitor].workspace;
 
-        let client = self.monitors[monitor].clients[workspace].remove(old_index);
-        self.monitors[monitor].clients[workspace].insert(new_index, client);
+        let client = self.monitors[monitor].workspaces[workspace].clients.remove(old_index);
+        self.monitors[monitor].workspaces[workspace].clients.insert(new_index, client);
     }
 
+    fn change_master_count(&mut self, delta: isize) {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+        let master_count = &mut self.monitors[monitor].workspaces[workspace].master_count;
+
+        *master_count = master_count.saturating_add_signed(delta);
+
+        self.tile_clients();
+    }
+
+    fn change_master_ratio(&mut self, delta: f32) {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+        let master_ratio = &mut self.monitors[monitor].workspaces[workspace].master_ratio;
+
+        *master_ratio = (*master_ratio + delta).clamp(0.1, 0.9);
+
+        self.tile_clients();
+    }
+
+    /*
+     * Gaps are limited to a quarter of the work area so there is always room left for the windows
+    */
+    fn change_gaps(&mut self, delta: i32) {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+        let limit = self.monitors[monitor].workarea.width.min(self.monitors[monitor].workarea.height) / 4;
+        let workspace = &mut self.monitors[monitor].workspaces[workspace];
+
+        workspace.inner_gap = workspace.inner_gap.saturating_add_signed(delta).min(limit);
+        workspace.outer_gap = workspace.outer_gap.saturating_add_signed(delta).min(limit);
+
+        self.tile_clients();
+    }
+
+    fn change_presel(&mut self, split: Split) {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        self.monitors[monitor].workspaces[workspace].presel = split;
+    }
+
+    fn change_split(&mut self, change: impl FnOnce(&mut Node, bool)) {
+        let monitor = self.current_monitor();
+        let workspace = self.monitors[monitor].workspace;
+
+        if let Some(tree) = &mut self.monitors[monitor].workspaces[workspace].tree {
+            if let Some((node, is_first)) = tree.parent_mut(self.window) {
+                change(node, is_first);
+            }
+        }
+
+        self.tile_clients();
+    }
+
+    /*
+     * Focusing an unmapped window is a BadMatch error, clients of hidden workspaces are never focused
+    */
     fn change_focus(&mut self, window: u64) -> Result<(), Box<dyn std::error::Error>> {
-        // self.display.raise_window(window);
+        let hidden = self.monitors.iter().any(|monitor| {
+            monitor.workspaces.iter().enumerate()
+                .any(|(index, workspace)| index != monitor.workspace && workspace.clients.iter().any(|c| c.window == window))
+        });
+
+        if hidden {
+            return Ok(());
+        }
+
+        if self.current_layout().raise_focused() {
+            self.display.raise_window(window);
+        }
+
         self.display.set_input_focus(window);
         self.display.set_focus_icccm(window);
         self.display.set_property_u64("_NET_ACTIVE_WINDOW", window, xlib::XA_WINDOW)?;
 
+        if let Some(monitor) = self.window_monitor(window) {
+            self.monitor = monitor;
+        }
+
         self.window = window;
+        self.set_desktops()?;
 
         Ok(())
     }
@@ -341,7 +1247,7 @@ impl WindowManager {
     fn change_focus_index(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
         let monitor = self.current_monitor();
         let workspace = self.monitors[monitor].workspace;
-        let window = self.monitors[monitor].clients[workspace][index].window;
+        let window = self.monitors[monitor].workspaces[workspace].clients[index].window;
 
         self.change_focus(window)?;
 
@@ -359,7 +1265,7 @@ impl WindowManager {
         loop {
             self.draw_bar()?;
 
-            let event = self.display.next_event();
+            let mut event = self.display.next_event();
 
             match unsafe { event.type_ } {
                 x11::xlib::KeyPress => {
@@ -388,7 +1294,7 @@ impl WindowManager {
                                         self.cleanup_bar();
 
                                         self.config = Config::load()?;
-                                        self.monitors = self.display.get_monitors(self.config.bar, &self.monitors)?;
+                                        self.monitors = self.display.get_monitors(&self.config, &self.monitors)?;
 
                                         self.setup()?;
 
@@ -402,8 +1308,12 @@ impl WindowManager {
                                     },
                                     Internal::FocusUp => {
                                         if let Some(index) = self.window_to_client_index(self.window) {
+                                            let monitor = self.current_monitor();
+
                                             if index > 0 {
                                                 self.change_focus_index(index - 1)?;
+                                            } else if self.current_layout().raise_focused() {
+                                                self.change_focus_index(self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients.len() - 1)?;
                                             }
                                         }
                                     },
@@ -411,8 +1321,10 @@ impl WindowManager {
                                         if let Some(index) = self.window_to_client_index(self.window) {
                                             let monitor = self.current_monitor();
 
-                                            if index < self.monitors[monitor].clients[self.monitors[monitor].workspace].len() - 1 {
+                                            if index < self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients.len() - 1 {
                                                 self.change_focus_index(index + 1)?;
+                                            } else if self.current_layout().raise_focused() {
+                                                self.change_focus_index(0)?;
                                             }
                                         }
                                     },
@@ -432,7 +1344,7 @@ impl WindowManager {
                                         if let Some(index) = self.window_to_client_index(self.window) {
                                             let monitor = self.current_monitor();
 
-                                            if index < self.monitors[monitor].clients[self.monitors[monitor].workspace].len() - 1 {
+                                            if index < self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients.len() - 1 {
                                                 self.move_client(index, index + 1);
 
                                                 self.tile_clients();
@@ -452,22 +1364,22 @@ impl WindowManager {
                                         let workspace = self.monitors[monitor].workspace;
 
                                         if self.is_tiled(window, monitor, workspace) {
-                                            self.monitors[monitor].clients[workspace] = self.monitors[monitor].clients[workspace].iter()
+                                            self.monitors[monitor].workspaces[workspace].clients = self.monitors[monitor].workspaces[workspace].clients.iter()
                                                 .map(|c| {
                                                     if c.window == window {
-                                                        Client::new(c.window, false)
+                                                        Client { tiled: false, ..*c }
                                                     } else {
-                                                        Client::new(c.window, c.tiled)
+                                                        *c
                                                     }
                                                 })
                                                 .collect::<Vec<Client>>();
                                         } else {
-                                            self.monitors[monitor].clients[workspace] = self.monitors[monitor].clients[workspace].iter()
+                                            self.monitors[monitor].workspaces[workspace].clients = self.monitors[monitor].workspaces[workspace].clients.iter()
                                                 .map(|c| {
                                                     if c.window == window {
-                                                        Client::new(c.window, true)
+                                                        Client { tiled: true, ..*c }
                                                     } else {
-                                                        Client::new(c.window, c.tiled)
+                                                        *c
                                                     }
                                                 })
                                                 .collect::<Vec<Client>>();
@@ -475,24 +1387,133 @@ impl WindowManager {
 
                                         self.tile_clients();
                                     },
+                                    Internal::ToggleSticky => {
+                                        let sticky = self.is_sticky(self.window);
+
+                                        self.set_sticky(self.window, !sticky)?;
+                                    },
+                                    Internal::IncMaster => {
+                                        self.change_master_count(1);
+                                    },
+                                    Internal::DecMaster => {
+                                        self.change_master_count(-1);
+                                    },
+                                    Internal::GrowMaster => {
+                                        self.change_master_ratio(0.05);
+                                    },
+                                    Internal::ShrinkMaster => {
+                                        self.change_master_ratio(-0.05);
+                                    },
+                                    Internal::PreselHorizontal => {
+                                        self.change_presel(Split::Horizontal);
+                                    },
+                                    Internal::PreselVertical => {
+                                        self.change_presel(Split::Vertical);
+                                    },
+                                    Internal::IncGaps => {
+                                        self.change_gaps(5);
+                                    },
+                                    Internal::DecGaps => {
+                                        self.change_gaps(-5);
+                                    },
+                                    Internal::MoveToWorkspace(workspace) => {
+                                        self.move_to_workspace(self.window, *workspace)?;
+                                    },
+                                    Internal::MoveToWorkspaceAndFollow(workspace) => {
+                                        let (window, workspace) = (self.window, *workspace);
+
+                                        if let Some(workspace) = self.transfer_to_workspace(window, workspace)? {
+                                            self.goto_workspace(workspace)?;
+
+                                            if self.window_monitor(window).is_some() {
+                                                self.change_focus(window)?;
+                                            }
+                                        }
+                                    },
+                                    Internal::WorkspaceNext => {
+                                        let monitor = self.current_monitor();
+
+                                        self.goto_workspace((self.monitors[monitor].workspace + 1) % self.monitors[monitor].workspaces.len())?;
+                                    },
+                                    Internal::WorkspacePrev => {
+                                        let monitor = self.current_monitor();
+                                        let workspaces = self.monitors[monitor].workspaces.len();
+
+                                        self.goto_workspace((self.monitors[monitor].workspace + workspaces - 1) % workspaces)?;
+                                    },
+                                    Internal::WorkspaceLast => {
+                                        let monitor = self.current_monitor();
+
+                                        self.goto_workspace(self.monitors[monitor].last_workspace)?;
+                                    },
+                                    Internal::ToggleView(workspace) => {
+                                        self.toggle_view(*workspace)?;
+                                    },
+                                    Internal::ToggleTag(workspace) => {
+                                        self.toggle_tag(self.window, *workspace)?;
+                                    },
+                                    Internal::MoveWorkspaceToMonitor(target) => {
+                                        self.move_workspace_to_monitor(*target)?;
+                                    },
+                                    Internal::FocusMonitor(target) => {
+                                        self.focus_monitor(*target)?;
+                                    },
+                                    Internal::SendToMonitor(target) => {
+                                        self.send_to_monitor(self.window, *target)?;
+                                    },
+                                    Internal::CycleLayout => {
+                                        let monitor = self.current_monitor();
+                                        let workspace = self.monitors[monitor].workspace;
+
+                                        self.set_layout((self.monitors[monitor].workspaces[workspace].layout + 1) % self.layouts.len());
+                                    },
+                                    Internal::SetLayout(name) => {
+                                        if let Some(layout) = self.layouts.iter().position(|l| l.name() == name) {
+                                            self.set_layout(layout);
+                                        } else {
+                                            println!("[+] unknown layout: {}", name);
+                                        }
+                                    },
+                                    Internal::RotateSplit => {
+                                        self.change_split(|node, _| node.rotate());
+                                    },
+                                    Internal::FlipSplit => {
+                                        self.change_split(|node, _| node.flip());
+                                    },
+                                    Internal::GrowSplit => {
+                                        self.change_split(|node, is_first| node.resize(if is_first { 0.05 } else { -0.05 }));
+                                    },
+                                    Internal::ShrinkSplit => {
+                                        self.change_split(|node, is_first| node.resize(if is_first { -0.05 } else { 0.05 }));
+                                    },
                                 }
                             },
                         }
                     }
 
-                    if (49..49 + 4).contains(&keysym) {
-                        self.goto_workspace(keysym as usize - 49)?;
+                    if (x11::keysym::XK_1..=x11::keysym::XK_9).take(self.workspace_keys()).any(|key| key == keysym) {
+                        let monitor = self.current_monitor();
+                        let workspace = (keysym - x11::keysym::XK_1) as usize;
+
+                        if self.config.back_and_forth && workspace == self.monitors[monitor].workspace {
+                            self.goto_workspace(self.monitors[monitor].last_workspace)?;
+                        } else {
+                            self.goto_workspace(workspace)?;
+                        }
                     }
                 },
                 x11::xlib::UnmapNotify => {
                     let window = unsafe { event.unmap.window };
-                    let monitor = self.current_monitor();
+                    let monitor = match self.window_monitor(window) {
+                        Some(monitor) => monitor,
+                        None => self.current_monitor(),
+                    };
                     let workspace = self.monitors[monitor].workspace;
 
                     /* Something wrong with this lol
                     for monitor in &mut self.monitors {
-                        for workspace in 0..monitor.clients.len() {
-                            monitor.clients[workspace] = monitor.clients[workspace].iter()
+                        for workspace in 0..monitor.workspaces.len() {
+                            monitor.workspaces[workspace].clients = monitor.workspaces[workspace].clients.iter()
                                 .filter(|c| c.window != window)
                                 .map(|c| *c)
                                 .collect::<Vec<Client>>();
@@ -500,17 +1521,27 @@ impl WindowManager {
                     }
                     */
 
-                    self.monitors[monitor].clients[workspace] = self.monitors[monitor].clients[self.monitors[monitor].workspace].iter()
+                    self.monitors[monitor].workspaces[workspace].clients = self.monitors[monitor].workspaces[self.monitors[monitor].workspace].clients.iter()
                         .filter(|c| c.window != window)
                         .map(|c| *c)
                         .collect::<Vec<Client>>();
 
+                    self.monitors[monitor].workspaces[workspace].remove_node(window);
+
                     if let Some(client) = self.monitors[monitor].fullscreen {
                         if client.window == window {
                             self.monitors[monitor].fullscreen = None;
                         }
                     }
 
+                    if self.docks.contains(&window) {
+                        self.docks.retain(|dock| *dock != window);
+                        self.update_struts();
+                    }
+
+                    self.prune_workspaces(monitor)?;
+                    self.set_desktops()?;
+
                     self.tile_clients();
                 },
                 x11::xlib::MapRequest => {
@@ -522,23 +1553,59 @@ impl WindowManager {
                         || self.display.atom_cmp(window, "_NET_WM_WINDOW_TYPE", "_NET_WM_WINDOW_TYPE_UTILITY")
                         || self.display.atom_cmp(window, "_NET_WM_WINDOW_TYPE", "_NET_WM_WINDOW_TYPE_SPLASH");
 
-                    if !self.monitors[monitor].clients[workspace].contains(&Client::new(window, ignored)) && !ignored {
-                        self.monitors[monitor].clients[workspace].push(Client::new(window, true));
+                    let view = self.monitors[monitor].view;
+
+                    if !self.monitors[monitor].workspaces[workspace].clients.iter().any(|c| c.window == window && !c.tiled) && !ignored {
+                        self.monitors[monitor].workspaces[workspace].clients.push(Client::new(window, true, view));
+                        self.monitors[monitor].workspaces[workspace].insert_node(self.window, window);
                     } else if !self.display.atom_cmp(window, "_NET_WM_WINDOW_TYPE", "_NET_WM_WINDOW_TYPE_DOCK") {
-                        self.monitors[monitor].clients[workspace].push(Client::new(window, false));
+                        self.monitors[monitor].workspaces[workspace].clients.push(Client::new(window, false, view));
                     }
 
                     self.display.map_window(window);
                     self.display.select_input(window);
                     self.display.set_wm_name(window, "ZovaWM");
+                    self.display.set_window_property_u64(window, "_NET_WM_DESKTOP", workspace as u64, xlib::XA_CARDINAL)?;
+
+                    if self.display.atom_cmp(window, "_NET_WM_STATE", "_NET_WM_STATE_STICKY") {
+                        self.set_sticky(window, true)?;
+                    }
+
+                    /*
+                     * docks are watched for changes to their struts
+                    */
+                    if self.display.atom_cmp(window, "_NET_WM_WINDOW_TYPE", "_NET_WM_WINDOW_TYPE_DOCK") {
+                        if !self.docks.contains(&window) {
+                            self.docks.push(window);
+                        }
+
+                        self.display.select_property_input(window);
+                        self.update_struts();
+                        self.set_desktops()?;
+                    }
 
                     self.window = window;
 
                     self.tile_clients();
                 },
+                x11::xlib::PropertyNotify => {
+                    let window = unsafe { event.property.window };
+                    let atom = unsafe { event.property.atom };
+
+                    if self.docks.contains(&window) && (atom == self.display.intern_atom("_NET_WM_STRUT") || atom == self.display.intern_atom("_NET_WM_STRUT_PARTIAL")) {
+                        self.update_struts();
+                        self.set_desktops()?;
+
+                        self.tile_clients();
+                    }
+                },
                 x11::xlib::EnterNotify => {
                     let window = unsafe { event.crossing.window };
 
+                    if let Some(monitor) = self.monitor_at(unsafe { event.crossing.x_root }, unsafe { event.crossing.y_root }) {
+                        self.monitor = monitor;
+                    }
+
                     self.change_focus(window)?;
                 },
                 x11::xlib::ClientMessage => {
@@ -547,15 +1614,33 @@ impl WindowManager {
                     let message_window = unsafe { event.client_message.window };
 
                     let state_fullscreen = self.display.intern_atom("_NET_WM_STATE_FULLSCREEN") as i64;
+                    let state_sticky = self.display.intern_atom("_NET_WM_STATE_STICKY") as i64;
 
                     if message_type == self.display.intern_atom("_NET_WM_STATE") {
                         if message_data.get_long(1) == state_fullscreen || message_data.get_long(2) == state_fullscreen {
                             self.toggle_fullscreen(message_window)?;
                         }
+
+                        /*
+                         * the first value is the action, 0 removes the state, 1 adds it and 2 toggles it
+                        */
+                        if message_data.get_long(1) == state_sticky || message_data.get_long(2) == state_sticky {
+                            let sticky = match message_data.get_long(0) {
+                                0 => false,
+                                1 => true,
+                                _ => !self.is_sticky(message_window),
+                            };
+
+                            self.set_sticky(message_window, sticky)?;
+                        }
                     }
                 },
                 x11::xlib::ButtonPress => {
                     let window = unsafe { event.key.subwindow };
+
+                    if let Some(monitor) = self.monitor_at(unsafe { event.button.x_root }, unsafe { event.button.y_root }) {
+                        self.monitor = monitor;
+                    }
                     let monitor = self.current_monitor();
                     let workspace = self.monitors[monitor].workspace;
 
@@ -591,7 +1676,11 @@ impl WindowManager {
                         }
                     }
                 },
-                _ => {},
+                _ => {
+                    if self.display.screen_changed(&mut event) {
+                        self.update_monitors()?;
+                    }
+                },
             }
         }
     }
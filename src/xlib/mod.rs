@@ -1,9 +1,12 @@
-use crate::wm::Monitor;
-use crate::wm::Bar;
+use crate::wm::{Monitor, Workspace};
+use crate::wm::{Bar, BAR_HEIGHT, BAR_MARGIN};
+use crate::layout::Rect;
+use crate::config::{Config, Padding};
 
 pub use x11::xlib::{XA_WINDOW, XA_CARDINAL, XA_ATOM};
 pub use x11::xlib::{Mod4Mask, Button1, Button3};
 use x11::xinerama;
+use x11::xrandr;
 use x11::xrender;
 use x11::xlib;
 use x11::xft;
@@ -18,6 +21,15 @@ struct WindowProperty {
     length: u64,
 }
 
+#[derive(Debug)]
+pub struct Output {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
 #[derive(Debug)]
 pub struct Pointer {
     pub x: i32,
@@ -27,6 +39,7 @@ pub struct Pointer {
 pub struct Display {
     ptr: *mut xlib::_XDisplay,
     screen: i32,
+    randr_event_base: Option<i32>,
     pub root: u64,
 }
 
@@ -49,9 +62,13 @@ impl Display {
 
                 xlib::XSetErrorHandler(Some(Self::handle_error));
 
+                let mut randr_event_base = 0;
+                let randr = xrandr::XRRQueryExtension(ptr, &mut randr_event_base, &mut 0) == xlib::True;
+
                 Ok(Display {
                     ptr,
                     screen,
+                    randr_event_base: randr.then_some(randr_event_base),
                     root,
                 })
             }
@@ -93,8 +110,9 @@ impl Display {
         }
     }
 
-    pub fn get_monitors(&mut self, bar: bool, old_monitors: &Vec<Monitor>) -> Result<Vec<Monitor>, Box<dyn std::error::Error>> {
+    pub fn get_monitors(&mut self, config: &Config, old_monitors: &Vec<Monitor>) -> Result<Vec<Monitor>, Box<dyn std::error::Error>> {
         let mut monitors: Vec<Monitor> = Vec::new();
+        let outputs = self.get_outputs();
 
         unsafe {
             if xinerama::XineramaIsActive(self.ptr) == xlib::True {
@@ -105,36 +123,137 @@ impl Display {
                 for index in 0..xmonitor_count {
                     let xmonitor =  *xmonitors.offset(index as isize);
 
+                    /*
+                     * xinerama screens have no name, they are matched with the output that has the same geometry
+                    */
+                    let name = outputs.iter()
+                        .find(|output| {
+                            output.x == xmonitor.x_org as i32 && output.y == xmonitor.y_org as i32
+                                && output.width == xmonitor.width as u32 && output.height == xmonitor.height as u32
+                        })
+                        .map(|output| output.name.clone())
+                        .unwrap_or_default();
+
                     monitors.push(Monitor {
                         x: xmonitor.x_org as i32,
                         y: xmonitor.y_org as i32,
                         width: xmonitor.width as u32,
                         height: xmonitor.height as u32,
-                        clients: if let Some(monitor) = old_monitors.get(index as usize) { monitor.clients.clone() } else { [Vec::new(), Vec::new(), Vec::new(), Vec::new()] },
+                        workspaces: if let Some(monitor) = Monitor::find(old_monitors, &name, index as usize) { Workspace::load(config, &name, &old_monitors[monitor].workspaces) } else { Workspace::load(config, &name, &[]) },
                         fullscreen: None,
                         workspace: 0,
-                        bar: if bar { Some(self.create_bar(xmonitor.x_org as i32, xmonitor.width as u32)?) } else { None },
+                        last_workspace: 0,
+                        view: 1,
+                        strut: Padding::default(),
+                        workarea: Rect::new(xmonitor.x_org as i32, xmonitor.y_org as i32, xmonitor.width as u32, xmonitor.height as u32),
+                        bar: if config.monitor_bar(&name) { Some(self.create_bar(xmonitor.x_org as i32, xmonitor.y_org as i32, xmonitor.width as u32)?) } else { None },
+                        name,
                     });
                 }
             } else {
                 let width = self.display_width();
+                let name = outputs.first().map(|output| output.name.clone()).unwrap_or_default();
 
                 monitors.push(Monitor {
                     x: 0,
                     y: 0,
                     width,
                     height: self.display_height(),
-                    clients: if let Some(monitor) = old_monitors.get(0) { monitor.clients.clone() } else { [Vec::new(), Vec::new(), Vec::new(), Vec::new()] },
+                    workspaces: if let Some(monitor) = Monitor::find(old_monitors, &name, 0) { Workspace::load(config, &name, &old_monitors[monitor].workspaces) } else { Workspace::load(config, &name, &[]) },
                     fullscreen: None,
                     workspace: 0,
-                    bar: if bar { Some(self.create_bar(0, width)?) } else { None },
+                    last_workspace: 0,
+                    view: 1,
+                    strut: Padding::default(),
+                    workarea: Rect::new(0, 0, width, self.display_height()),
+                    bar: if config.monitor_bar(&name) { Some(self.create_bar(0, 0, width)?) } else { None },
+                    name,
                 });
             }
         }
 
+        for monitor in &monitors {
+            println!("[+] monitor: {:?} {}x{}+{}+{}", monitor.name, monitor.width, monitor.height, monitor.x, monitor.y);
+        }
+
         Ok(monitors)
     }
 
+    /*
+     * Every RandR output that is currently driving a crtc
+    */
+    pub fn get_outputs(&mut self) -> Vec<Output> {
+        let mut outputs: Vec<Output> = Vec::new();
+
+        if self.randr_event_base.is_none() {
+            return outputs;
+        }
+
+        unsafe {
+            let resources = xrandr::XRRGetScreenResourcesCurrent(self.ptr, self.root);
+
+            if resources.is_null() {
+                return outputs;
+            }
+
+            for index in 0..(*resources).noutput {
+                let output_info = xrandr::XRRGetOutputInfo(self.ptr, resources, *(*resources).outputs.offset(index as isize));
+
+                if output_info.is_null() {
+                    continue;
+                }
+
+                if (*output_info).crtc != 0 {
+                    let crtc_info = xrandr::XRRGetCrtcInfo(self.ptr, resources, (*output_info).crtc);
+
+                    if !crtc_info.is_null() {
+                        let name = std::slice::from_raw_parts((*output_info).name as *const u8, (*output_info).nameLen as usize);
+
+                        outputs.push(Output {
+                            name: String::from_utf8_lossy(name).to_string(),
+                            x: (*crtc_info).x,
+                            y: (*crtc_info).y,
+                            width: (*crtc_info).width,
+                            height: (*crtc_info).height,
+                        });
+
+                        xrandr::XRRFreeCrtcInfo(crtc_info);
+                    }
+                }
+
+                xrandr::XRRFreeOutputInfo(output_info);
+            }
+
+            xrandr::XRRFreeScreenResources(resources);
+        }
+
+        outputs
+    }
+
+    pub fn select_screen_change_input(&mut self) {
+        if self.randr_event_base.is_some() {
+            unsafe {
+                xrandr::XRRSelectInput(self.ptr, self.root, xrandr::RRScreenChangeNotifyMask);
+            }
+        }
+    }
+
+    /*
+     * Xlib caches the screen size, it has to be updated before the monitors are queried again
+    */
+    pub fn screen_changed(&mut self, event: &mut xlib::XEvent) -> bool {
+        match self.randr_event_base {
+            Some(base) if event.get_type() == base + xrandr::RRScreenChangeNotify => {
+                unsafe {
+                    xrandr::XRRUpdateConfiguration(event);
+                }
+
+                true
+            },
+            _ => false,
+        }
+    }
+
     pub fn query_pointer(&mut self) -> Pointer {
         unsafe {
             let mut root_return = self.root;
@@ -160,6 +279,12 @@ impl Display {
         }
     }
 
+    pub fn warp_pointer(&mut self, x: i32, y: i32) {
+        unsafe {
+            xlib::XWarpPointer(self.ptr, 0, self.root, 0, 0, 0, 0, x, y);
+        }
+    }
+
     pub fn frame_window(&mut self, window: u64) {
         unsafe {
             let mut attr: xlib::XWindowAttributes = mem::zeroed();
@@ -203,15 +328,15 @@ impl Display {
         }
     }
 
-    pub fn create_bar(&mut self, x: i32, width: u32) -> Result<Bar, Box<dyn std::error::Error>> {
+    pub fn create_bar(&mut self, x: i32, y: i32, width: u32) -> Result<Bar, Box<dyn std::error::Error>> {
         unsafe {
             let window = xlib::XCreateSimpleWindow(
                 self.ptr,
                 self.root,
-                x + 10,
-                10,
-                width - 20,
-                30,
+                x + BAR_MARGIN,
+                y + BAR_MARGIN,
+                width - BAR_MARGIN as u32 * 2,
+                BAR_HEIGHT,
                 0,
                 0x0000ff,
                 0x0d1617,
@@ -344,11 +469,15 @@ impl Display {
     }
 
     pub fn set_property_u64(&mut self, property: &str, value: u64, type_: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.set_window_property_u64(self.root, property, value, type_)
+    }
+
+    pub fn set_window_property_u64(&mut self, window: u64, property: &str, value: u64, type_: u64) -> Result<(), Box<dyn std::error::Error>> {
         unsafe {
             let p_atom = xlib::XInternAtom(self.ptr, Self::null_terminate(property).as_ptr() as *const i8, xlib::False);
 
-            xlib::XDeleteProperty(self.ptr, self.root, p_atom);
-            xlib::XChangeProperty(self.ptr, self.root, p_atom, type_, 32, xlib::PropModeReplace, (&value as *const u64) as *const u8, 1);
+            xlib::XDeleteProperty(self.ptr, window, p_atom);
+            xlib::XChangeProperty(self.ptr, window, p_atom, type_, 32, xlib::PropModeReplace, (&value as *const u64) as *const u8, 1);
         }
 
         Ok(())
@@ -366,7 +495,7 @@ impl Display {
         }
     }
 
-    fn get_window_property(&mut self, window: u64, atom: u64) -> WindowProperty {
+    fn get_window_property(&mut self, window: u64, atom: u64, type_: u64) -> WindowProperty {
         unsafe {
             let mut a_atom: xlib::Atom = mem::zeroed();
             let mut a_format = 0;
@@ -381,7 +510,7 @@ impl Display {
                 0,
                 i64::MAX,
                 xlib::False,
-                xlib::XA_ATOM,
+                type_,
                 &mut a_atom,
                 &mut a_format,
                 &mut length,
@@ -403,7 +532,7 @@ impl Display {
         unsafe {
             let p_atom = xlib::XInternAtom(self.ptr, Self::null_terminate(property).as_ptr() as *const i8, xlib::False);
             let v_atom = xlib::XInternAtom(self.ptr, Self::null_terminate(value).as_ptr() as *const i8, xlib::True);
-            let window_property = self.get_window_property(window, p_atom);
+            let window_property = self.get_window_property(window, p_atom, xlib::XA_ATOM);
 
             for index in 0..window_property.length {
                 let atom = *(window_property.data.offset(index as isize) as *mut xlib::Atom);
@@ -417,6 +546,23 @@ impl Display {
         }
     }
 
+    pub fn get_cardinals(&mut self, window: u64, property: &str) -> Vec<u64> {
+        unsafe {
+            let p_atom = xlib::XInternAtom(self.ptr, Self::null_terminate(property).as_ptr() as *const i8, xlib::False);
+            let window_property = self.get_window_property(window, p_atom, xlib::XA_CARDINAL);
+
+            let cardinals = (0..window_property.length)
+                .map(|index| *(window_property.data as *mut u64).offset(index as isize))
+                .collect::<Vec<u64>>();
+
+            if !window_property.data.is_null() {
+                xlib::XFree(window_property.data as *mut _);
+            }
+
+            cardinals
+        }
+    }
+
     pub fn set_wm_name(&mut self, window: u64, name: &str) {
         unsafe {
             let mut text_property: xlib::XTextProperty = mem::zeroed();
@@ -466,6 +612,12 @@ impl Display {
         }
     }
 
+    pub fn destroy_window(&mut self, window: u64) {
+        unsafe {
+            xlib::XDestroyWindow(self.ptr, window);
+        }
+    }
+
     pub fn map_window(&mut self, window: u64) {
         unsafe {
             xlib::XMapWindow(self.ptr, window);
@@ -478,9 +630,12 @@ impl Display {
         }
     }
 
+    /*
+     * A width or height of zero is a BadValue error which would be fatal
+    */
     pub fn resize_window(&mut self, window: u64, x: i32, y: i32, width: u32, height: u32) {
         unsafe {
-            xlib::XMoveResizeWindow(self.ptr, window, x, y, width, height);
+            xlib::XMoveResizeWindow(self.ptr, window, x, y, width.max(1), height.max(1));
         }
     }
 
@@ -534,6 +689,40 @@ impl Display {
         }
     }
 
+    pub fn set_workarea(&mut self, window: u64, workarea: &[u64]) {
+        unsafe {
+            xlib::XChangeProperty(
+                self.ptr,
+                window,
+                self.intern_atom("_NET_WORKAREA"),
+                xlib::XA_CARDINAL,
+                32,
+                xlib::PropModeReplace,
+                workarea.as_ptr() as *const u8,
+                workarea.len() as i32,
+            );
+        }
+    }
+
+    pub fn set_desktop_names(&mut self, window: u64, names: &[String]) {
+        unsafe {
+            let names = names.iter()
+                .map(|name| Self::null_terminate(name))
+                .collect::<String>();
+
+            xlib::XChangeProperty(
+                self.ptr,
+                window,
+                self.intern_atom("_NET_DESKTOP_NAMES"),
+                self.intern_atom("UTF8_STRING"),
+                8,
+                xlib::PropModeReplace,
+                names.as_ptr(),
+                names.len() as i32,
+            );
+        }
+    }
+
     pub fn set_net_supported(&mut self, window: u64) {
         unsafe {
             let supported = [
@@ -545,11 +734,17 @@ impl Display {
 
                 self.intern_atom("_NET_NUMBER_OF_DESKTOPS"),
                 self.intern_atom("_NET_CURRENT_DESKTOP"),
+                self.intern_atom("_NET_DESKTOP_NAMES"),
                 self.intern_atom("_NET_DESKTOP_VIEWPORT"),
+                self.intern_atom("_NET_WORKAREA"),
+                self.intern_atom("_NET_WM_DESKTOP"),
+                self.intern_atom("_NET_WM_STRUT"),
+                self.intern_atom("_NET_WM_STRUT_PARTIAL"),
 
                 self.intern_atom("_NET_WM_STATE"),
                 self.intern_atom("_NET_WM_STATE_MODAL"),
                 self.intern_atom("_NET_WM_STATE_FULLSCREEN"),
+                self.intern_atom("_NET_WM_STATE_STICKY"),
 
                 self.intern_atom("_NET_WM_WINDOW_TYPE"),
                 self.intern_atom("_NET_WM_WINDOW_TYPE_DOCK"),
@@ -642,6 +837,12 @@ impl Display {
         }
     }
 
+    pub fn select_property_input(&mut self, window: u64) {
+        unsafe {
+            xlib::XSelectInput(self.ptr, window, xlib::SubstructureNotifyMask | xlib::SubstructureRedirectMask | xlib::EnterWindowMask | xlib::PropertyChangeMask);
+        }
+    }
+
     pub fn set_input_focus(&mut self, window: u64) {
         unsafe {
             xlib::XSetInputFocus(self.ptr, window, xlib::RevertToParent, xlib::CurrentTime);